@@ -14,7 +14,7 @@ fn main() {
     App::new()
         .add_plugins((
             MinimalPlugins,
-            AsyncSystemPlugin
+            AsyncSystemPlugin::default()
         ))
         .add_systems(Startup, setup)
         .run();
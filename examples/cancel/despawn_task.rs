@@ -22,7 +22,7 @@ fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
-            AsyncSystemPlugin
+            AsyncSystemPlugin::default()
         ))
         .add_systems(Startup, setup)
         .add_systems(Update, cancel)
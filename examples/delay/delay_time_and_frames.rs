@@ -16,7 +16,7 @@ fn main() {
         .add_plugins((
             DefaultPlugins.build().disable::<LogPlugin>(),
             FramepacePlugin,
-            AsyncSystemPlugin
+            AsyncSystemPlugin::default()
         ))
         .add_systems(Startup, setup)
         .run();
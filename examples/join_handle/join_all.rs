@@ -11,7 +11,7 @@ fn main() {
         .add_plugins((
             DefaultPlugins,
             WorldInspectorPlugin::new(),
-            AsyncSystemPlugin
+            AsyncSystemPlugin::default()
         ))
         .add_systems(Startup, setup_async_systems)
         .run();
@@ -7,7 +7,7 @@ fn main() {
         .add_state::<ExampleState>()
         .add_plugins((
             MinimalPlugins,
-            AsyncSystemPlugin
+            AsyncSystemPlugin::default()
         ))
         .add_systems(Startup, setup)
         .run();
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use bevy::app::{App, Startup, Update};
+use bevy::DefaultPlugins;
+use bevy::math::{Quat, Vec2, Vec3};
+use bevy::prelude::{Camera2dBundle, Color, Commands, Sprite, Transform};
+use bevy::sprite::SpriteBundle;
+use bevy::utils::default;
+
+use bevy_async_system::AsyncSystemPlugin;
+use bevy_async_system::ext::spawn_async_system::SpawnAsyncSystem;
+use bevy_async_system::runner::tween::{self, Easing};
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            AsyncSystemPlugin::default()
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let shape = commands.spawn(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(Vec2::new(50., 50.)),
+            color: Color::BLUE,
+            ..default()
+        },
+        ..default()
+    }).id();
+
+    commands.spawn_async(move |schedules| async move {
+        let up = Transform::from_xyz(0., 300., 0.);
+        schedules.add_system(Update, tween::transform_to(shape, up, Duration::from_secs(1), Easing::QuadOut)).await;
+
+        let right = Transform::from_xyz(500., 300., 0.).with_rotation(Quat::from_rotation_z(std::f32::consts::PI));
+        schedules.add_system(Update, tween::transform_to(shape, right, Duration::from_secs(2), Easing::SineInOut)).await;
+        schedules.add_system(Update, tween::lerp(shape, right, right.with_scale(Vec3::splat(2.)), Duration::from_secs(1), Easing::CubicInOut)).await;
+    });
+}
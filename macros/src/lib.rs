@@ -0,0 +1,191 @@
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{Expr, FnArg, Ident, ItemFn, Pat, parse_macro_input, Stmt, Token};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+
+/// Turns an `async fn` taking [`AsyncSchedules`] into an async system.
+///
+/// Inside the function, `ecs!(schedule_label, system)` runs `system` once in the schedule and returns its output.
+/// It expands to `schedules.add_system(schedule_label, once::run(system)).await`,
+/// where `schedules` is the argument of the function.
+///
+/// A unit struct named after the function in `PascalCase` is declared as the task type.
+/// `Type::spawn(&mut commands)` spawns the task, and the type implements `AsyncTask`,
+/// so it can also be registered with `app.register_async_task::<Type>()`.
+/// The name of the struct can be given as the argument of the attribute: `#[async_system(OpeningCutscene)]`.
+///
+/// ```ignore
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// #[async_system]
+/// async fn cutscene(schedules: AsyncSchedules) {
+///     let count = ecs!(Update, |q: Query<&Transform>| q.iter().count());
+///     ecs!(Update, move || println!("{count} entities have a transform"));
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     Cutscene::spawn(&mut commands);
+/// }
+/// ```
+///
+/// [`AsyncSchedules`]: https://docs.rs/bevy_async_system/latest/bevy_async_system/async_schedules/struct.AsyncSchedules.html
+#[proc_macro_attribute]
+pub fn async_system(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let task_name = parse_macro_input!(attr as Option<Ident>);
+    let mut item_fn = parse_macro_input!(item as ItemFn);
+
+    if item_fn.sig.asyncness.is_none() {
+        return syn::Error::new(item_fn.sig.fn_token.span(), "`#[async_system]` can only be used on an `async fn`")
+            .into_compile_error()
+            .into();
+    }
+    let schedules = match schedules_ident(&item_fn) {
+        Ok(schedules) => schedules,
+        Err(error) => return error.into_compile_error().into()
+    };
+
+    let mut expander = EcsExpander {
+        schedules,
+        errors: Vec::new(),
+    };
+    expander.visit_block_mut(&mut item_fn.block);
+    if let Some(error) = expander.errors.into_iter().reduce(|mut errors, error| {
+        errors.combine(error);
+        errors
+    }) {
+        return error.into_compile_error().into();
+    }
+
+    let fn_name = &item_fn.sig.ident;
+    let vis = &item_fn.vis;
+    let task_name = task_name.unwrap_or_else(|| Ident::new(&pascal_case(&fn_name.to_string()), fn_name.span()));
+    let doc = format!("The async task declared by [`{fn_name}`].");
+
+    quote! {
+        #item_fn
+
+        #[doc = #doc]
+        #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+        #vis struct #task_name;
+
+        impl #task_name {
+            /// Spawns the task.
+            #vis fn spawn<'w, 's, 'a>(commands: &'a mut ::bevy_async_system::__private::Commands<'w, 's>) -> ::bevy_async_system::__private::EntityCommands<'w, 's, 'a> {
+                ::bevy_async_system::ext::spawn_async_system::SpawnAsyncSystem::spawn_async(commands, #fn_name)
+            }
+        }
+
+        #[::bevy_async_system::__private::async_trait]
+        impl ::bevy_async_system::async_task::AsyncTask for #task_name {
+            async fn run(self, schedules: ::bevy_async_system::async_schedules::AsyncSchedules) {
+                #fn_name(schedules).await
+            }
+        }
+    }
+        .into()
+}
+
+
+fn schedules_ident(item_fn: &ItemFn) -> syn::Result<Ident> {
+    let mut inputs = item_fn.sig.inputs.iter();
+    match (inputs.next(), inputs.next()) {
+        (Some(FnArg::Typed(arg)), None) => match &*arg.pat {
+            Pat::Ident(pat) => Ok(pat.ident.clone()),
+            pat => Err(syn::Error::new(pat.span(), "the argument must be a plain identifier, such as `schedules: AsyncSchedules`"))
+        },
+        _ => Err(syn::Error::new(item_fn.sig.span(), "`#[async_system]` functions must take exactly one argument of type `AsyncSchedules`"))
+    }
+}
+
+
+fn pascal_case(name: &str) -> String {
+    name
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+
+struct EcsArgs {
+    schedule_label: Expr,
+    system: Expr,
+}
+
+
+impl Parse for EcsArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let schedule_label = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let system = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self {
+            schedule_label,
+            system,
+        })
+    }
+}
+
+
+struct EcsExpander {
+    schedules: Ident,
+    errors: Vec<syn::Error>,
+}
+
+
+impl EcsExpander {
+    fn expand(&mut self, mac: &syn::Macro) -> Option<Expr> {
+        if !mac.path.is_ident("ecs") {
+            return None;
+        }
+
+        let EcsArgs { schedule_label, mut system } = match mac.parse_body() {
+            Ok(args) => args,
+            Err(error) => {
+                self.errors.push(error);
+                return None;
+            }
+        };
+        self.visit_expr_mut(&mut system);
+
+        let schedules = &self.schedules;
+        Some(syn::parse_quote_spanned! {mac.span()=>
+            #schedules.add_system(#schedule_label, ::bevy_async_system::runner::once::run(#system)).await
+        })
+    }
+}
+
+
+impl VisitMut for EcsExpander {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Macro(expr_macro) = expr {
+            if let Some(expanded) = self.expand(&expr_macro.mac) {
+                *expr = expanded;
+                return;
+            }
+        }
+        syn::visit_mut::visit_expr_mut(self, expr);
+    }
+
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        if let Stmt::Macro(stmt_macro) = stmt {
+            if let Some(expanded) = self.expand(&stmt_macro.mac) {
+                let semi = stmt_macro.semi_token;
+                *stmt = syn::parse_quote_spanned! {stmt_macro.span()=> #expanded #semi };
+                return;
+            }
+        }
+        syn::visit_mut::visit_stmt_mut(self, stmt);
+    }
+}
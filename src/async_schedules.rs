@@ -1,15 +1,114 @@
+use std::any::Any;
 use std::future::Future;
+use std::panic::{AssertUnwindSafe, Location};
+use std::pin::Pin;
+use std::sync::Arc;
 
+use bevy::app::AppLabel;
 use bevy::ecs::schedule::ScheduleLabel;
-use bevy::prelude::{Component, Deref, DerefMut};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::{Bundle, Component, Deref, DerefMut, Schedules};
 use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy::utils::synccell::SyncCell;
 use futures::channel::mpsc::{Receiver, Sender};
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
+use futures_lite::future::{block_on, poll_once};
 
-use crate::runner::{IntoAsyncScheduleCommand, AsyncScheduleCommands};
+use crate::ext::spawn_async_system::async_task_bundle;
+use crate::runner::{AsyncSchedule, AsyncScheduleCommand, AsyncScheduleCommands, AwaitingOutput, IntoAsyncScheduleCommand};
 
-#[derive(Component, Deref, DerefMut)]
-pub struct TaskHandle(pub(crate) Task<()>);
+pub(crate) type BoxedTaskFuture = Pin<Box<dyn Future<Output=()> + Send>>;
+
+type BoxedOutcomeFuture = Pin<Box<dyn Future<Output=TaskOutcome> + Send>>;
+
+
+/// Why a task ended.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TaskOutcome {
+    /// The async body returned.
+    Completed,
+
+    /// The async body panicked with the message.
+    Panicked(String),
+}
+
+
+#[derive(Component)]
+pub struct TaskHandle(pub(crate) TaskState);
+
+
+pub(crate) enum TaskState {
+    /// The future is polled directly by the plugin systems on the main thread.
+    Inline(SyncCell<BoxedOutcomeFuture>),
+
+    /// The future has been spawned on a task pool.
+    Spawned(Task<TaskOutcome>),
+}
+
+
+impl TaskHandle {
+    #[inline]
+    pub(crate) fn inline(future: impl Future<Output=()> + Send + 'static) -> Self {
+        Self(TaskState::Inline(SyncCell::new(Box::pin(catch_panic(future)))))
+    }
+
+
+    /// The future of `task` must be wrapped by [`catch_panic`].
+    #[inline]
+    pub(crate) const fn spawned(task: Task<TaskOutcome>) -> Self {
+        Self(TaskState::Spawned(task))
+    }
+
+
+    #[inline]
+    pub(crate) const fn is_inline(&self) -> bool {
+        matches!(self.0, TaskState::Inline(_))
+    }
+
+
+    /// Polls the task once and returns the outcome if it has finished.
+    pub(crate) fn poll(&mut self) -> Option<TaskOutcome> {
+        match &mut self.0 {
+            TaskState::Inline(future) => block_on(poll_once(future.get())),
+            TaskState::Spawned(task) => block_on(poll_once(task))
+        }
+    }
+
+
+    /// Moves the inline future onto [`AsyncComputeTaskPool`].
+    pub(crate) fn spawn_on_pool(&mut self) {
+        if let TaskState::Inline(future) = &mut self.0 {
+            let future = std::mem::replace(future.get(), Box::pin(futures::future::pending()));
+            self.0 = TaskState::Spawned(AsyncComputeTaskPool::get().spawn(future));
+        }
+    }
+}
+
+
+/// Pauses the task entity it is attached to.
+///
+/// The systems awaited by the task stop running until the component is removed,
+/// and with [`TaskExecution::Deterministic`](crate::TaskExecution::Deterministic) the task is not polled either.
+#[derive(Component, Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct PausedTask;
+
+
+/// Turns a panic of the future into [`TaskOutcome::Panicked`], so that it does not take down the thread polling it.
+pub(crate) async fn catch_panic(future: impl Future<Output=()>) -> TaskOutcome {
+    match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(()) => TaskOutcome::Completed,
+        Err(payload) => TaskOutcome::Panicked(panic_message(payload.as_ref()))
+    }
+}
+
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
 
 
 #[derive(Component, Deref, DerefMut)]
@@ -23,26 +122,307 @@ pub struct AsyncSchedules {
 
 
 impl AsyncSchedules {
+    #[track_caller]
     pub fn add_system<Out: Send + 'static>(
         &self,
         schedule_label: impl ScheduleLabel + Clone,
         into_schedule_command: impl IntoAsyncScheduleCommand<Out>,
     ) -> impl Future<Output=Out> {
         let (tx, rx) = futures::channel::mpsc::channel(1);
-        self.schedulers.push(into_schedule_command.into_schedule_command(TaskSender(tx), schedule_label));
+        self.push(into_schedule_command
+                      .into_schedule_command(TaskSender(tx), schedule_label.clone())
+                      .with_schedule_label(schedule_label), rx)
+    }
 
-        create_output_future(rx)
+
+    /// Adds the system to the schedule of the app labeled `app_label` instead of the world in which the task runs.
+    ///
+    /// The app must receive the systems via [`RouteAsyncSystems`](crate::ext::route_async_systems::RouteAsyncSystems).
+    #[track_caller]
+    pub fn add_system_to<Out: Send + 'static>(
+        &self,
+        app_label: impl AppLabel,
+        schedule_label: impl ScheduleLabel + Clone,
+        into_schedule_command: impl IntoAsyncScheduleCommand<Out>,
+    ) -> impl Future<Output=Out> {
+        let (tx, rx) = futures::channel::mpsc::channel(1);
+        self.push(into_schedule_command
+                      .into_schedule_command(TaskSender(tx), schedule_label.clone())
+                      .with_schedule_label(schedule_label)
+                      .with_target(app_label), rx)
+    }
+
+
+    /// Spawns a child task and returns a future that outputs the value returned by the child.
+    ///
+    /// The child starts in the next setup schedule whether or not the returned future is awaited,
+    /// so several children can run concurrently and be awaited afterwards.
+    /// The child task entity is spawned as a child of this task entity,
+    /// so it is cancelled when this task finishes, is restarted or is despawned recursively.
+    ///
+    /// If the child panics, the panic is propagated to this task when the future is awaited.
+    /// If the child is despawned before it finishes, this task panics when the future is awaited.
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands) {
+    ///     commands.spawn_async(|schedules| async move {
+    ///         let _blink = schedules.spawn_child(|schedules| async move {
+    ///             loop {
+    ///                 schedules.add_system(Update, delay::frames(30)).await;
+    ///                 schedules.add_system(Update, once::run(|| println!("blink"))).await;
+    ///             }
+    ///         });
+    ///         let loaded = schedules.spawn_child(|schedules| async move {
+    ///             schedules.add_system(Update, delay::frames(120)).await;
+    ///             "level 1"
+    ///         });
+    ///
+    ///         // The blinking child is cancelled when this task finishes.
+    ///         let level = loaded.await;
+    ///         println!("{level} is loaded");
+    ///     });
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn spawn_child<Out, F>(&self, f: impl FnOnce(AsyncSchedules) -> F) -> impl Future<Output=Out>
+        where
+            Out: Send + 'static,
+            F: Future<Output=Out> + Send + 'static
+    {
+        let (tx, rx) = futures::channel::mpsc::channel(1);
+        let tx = ChildSender(Some(tx));
+        let command = child_task_command(f, move |output| tx.send(output.map_err(ChildError::Panicked)));
+        let output = self.push(command, rx);
+
+        async move {
+            match output.await {
+                Ok(output) => output,
+                Err(ChildError::Panicked(message)) => panic!("a child task panicked: {message}"),
+                Err(ChildError::Despawned) => panic!("a child task was despawned before it finished")
+            }
+        }
+    }
+
+
+    #[track_caller]
+    pub(crate) fn push<Out: Send + 'static>(&self, command: AsyncScheduleCommand, rx: Receiver<Out>) -> impl Future<Output=Out> {
+        let awaiting = Arc::new(AwaitingOutput::default());
+        self.schedulers.push(command.with_caller(Location::caller(), Arc::downgrade(&awaiting)));
+
+        create_output_future(rx, awaiting)
     }
 }
 
 
-#[inline]
-fn create_output_future<Out: Send + 'static>(mut rx: Receiver<Out>) -> impl Future<Output=Out> {
-    AsyncComputeTaskPool::get().spawn(async move {
-        loop {
-            if let Some(output) = rx.next().await {
-                return output;
+enum ChildError {
+    Panicked(String),
+    Despawned,
+}
+
+
+/// Sends the outcome of a child task to [`AsyncSchedules::spawn_child`].
+///
+/// If the child task is dropped before it finishes, [`ChildError::Despawned`] is sent instead,
+/// so the parent does not wait forever.
+struct ChildSender<Out>(Option<Sender<Result<Out, ChildError>>>);
+
+
+impl<Out> ChildSender<Out> {
+    fn send(mut self, output: Result<Out, ChildError>) {
+        if let Some(mut tx) = self.0.take() {
+            let _ = tx.try_send(output);
+        }
+    }
+}
+
+
+impl<Out> Drop for ChildSender<Out> {
+    fn drop(&mut self) {
+        if let Some(mut tx) = self.0.take() {
+            let _ = tx.try_send(Err(ChildError::Despawned));
+        }
+    }
+}
+
+
+/// Creates the command that spawns a child task.
+///
+/// `notify` receives the output of the child, or the panic message if the child panicked.
+/// The panic is not resumed in the child, since `notify` hands it over to whoever awaits the child.
+pub(crate) fn child_task_command<Out, F>(
+    f: impl FnOnce(AsyncSchedules) -> F,
+    notify: impl FnOnce(Result<Out, String>) + Send + 'static,
+) -> AsyncScheduleCommand
+    where
+        Out: Send + 'static,
+        F: Future<Output=Out> + Send + 'static
+{
+    AsyncScheduleCommand::new(SpawnChildTask(async_task_bundle(move |schedules| {
+        let future = f(schedules);
+        async move {
+            match AssertUnwindSafe(future).catch_unwind().await {
+                Ok(output) => notify(Ok(output)),
+                Err(payload) => notify(Err(panic_message(payload.as_ref())))
             }
         }
-    })
-}
\ No newline at end of file
+    })))
+}
+
+
+/// Inserts the components of the child task into the runner entity,
+/// which is already a child of the task entity that spawned it.
+struct SpawnChildTask<B>(B);
+
+
+impl<B: Bundle> AsyncSchedule for SpawnChildTask<B> {
+    fn initialize(self: Box<Self>, entity_commands: &mut EntityCommands, _: &mut Schedules) {
+        entity_commands.insert(self.0);
+    }
+
+
+    fn kind(&self) -> &'static str {
+        "spawn_child"
+    }
+}
+
+
+/// The output is received directly inside the awaiting task,
+/// so the continuation does not depend on another task being scheduled.
+///
+/// `awaiting` is held until the output is received, so that diagnostics can tell whether the system is still awaited.
+#[inline]
+async fn create_output_future<Out: Send + 'static>(mut rx: Receiver<Out>, awaiting: Arc<AwaitingOutput>) -> Out {
+    match rx.next().await {
+        Some(output) => {
+            drop(awaiting);
+            output
+        }
+        None => match awaiting.rejection() {
+            Some(error) => panic!("{error}"),
+            // The runner was dropped without sending; the task can never continue.
+            None => futures::future::pending().await
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::hierarchy::{DespawnRecursiveExt, Parent};
+    use bevy::prelude::{Entity, ResMut, Resource, With};
+
+    use crate::async_schedules::TaskHandle;
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::{once, wait};
+    use crate::test_util::{new_app, new_deterministic_app};
+
+    #[derive(Resource, Default)]
+    struct Sum(u32);
+
+
+    #[test]
+    fn await_child_outputs() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Sum>();
+        let task = app.spawn_async(|schedules| async move {
+            let a = schedules.spawn_child(|schedules| async move {
+                schedules.add_system(Update, once::run(|| 1)).await
+            });
+            let b = schedules.spawn_child(|schedules| async move {
+                schedules.add_system(Update, once::run(|| 2)).await
+            });
+            let sum = a.await + b.await;
+            schedules.add_system(Update, once::run(move |mut total: ResMut<Sum>| total.0 = sum)).await;
+        }).id();
+
+        app.update();
+        app.update();
+        let children = app
+            .world
+            .query_filtered::<&Parent, With<TaskHandle>>()
+            .iter(&app.world)
+            .filter(|parent| parent.get() == task)
+            .count();
+        assert_eq!(children, 2);
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Sum>().0, 3);
+        assert!(app.world.get_entity(task).is_none());
+    }
+
+
+    #[test]
+    fn cancel_children_when_parent_finishes() {
+        let mut app = new_deterministic_app();
+        let task = app.spawn_async(|schedules| async move {
+            let _forever = schedules.spawn_child(|schedules| async move {
+                schedules.add_system(Update, wait::until(|| false)).await;
+            });
+            schedules.add_system(Update, once::run(|| {})).await;
+            schedules.add_system(Update, once::run(|| {})).await;
+        }).id();
+
+        app.update();
+        app.update();
+        assert_eq!(app.world.query::<&TaskHandle>().iter(&app.world).len(), 2);
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app.world.get_entity(task).is_none());
+        assert_eq!(app.world.query::<&TaskHandle>().iter(&app.world).len(), 0);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "a child task was despawned before it finished")]
+    fn panic_if_child_is_despawned() {
+        let mut app = new_deterministic_app();
+        let task = app.spawn_async(|schedules| async move {
+            let child = schedules.spawn_child(|schedules| async move {
+                schedules.add_system(Update, wait::until(|| false)).await;
+            });
+            child.await;
+        }).id();
+
+        app.update();
+        app.update();
+        let child = app
+            .world
+            .query_filtered::<(Entity, &Parent), With<TaskHandle>>()
+            .iter(&app.world)
+            .find(|(_, parent)| parent.get() == task)
+            .map(|(child, _)| child)
+            .unwrap();
+        app.world.entity_mut(child).despawn_recursive();
+
+        for _ in 0..5 {
+            app.update();
+        }
+    }
+
+
+    #[test]
+    #[should_panic(expected = "a child task panicked: broken")]
+    fn propagate_child_panic() {
+        let mut app = new_app();
+        app.spawn_async(|schedules| async move {
+            let child = schedules.spawn_child(|_| async move {
+                panic!("broken");
+            });
+            child.await;
+        });
+
+        // The parent panics on the task pool, and the panic is resumed once the task is removed.
+        for _ in 0..1000 {
+            app.update();
+            std::thread::yield_now();
+        }
+    }
+}
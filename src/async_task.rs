@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use bevy::hierarchy::BuildWorldChildren;
+use bevy::prelude::{Added, Commands, Component, Entity, Event, Events, Query, ResMut, World};
+
+use crate::async_schedules::AsyncSchedules;
+use crate::ext::spawn_async_system::async_task_bundle;
+
+/// A reusable definition of an async task.
+///
+/// Register the type with [`RegisterAsyncTask::register_async_task`](crate::ext::register_async_task::RegisterAsyncTask::register_async_task),
+/// then spawn it by sending [`SpawnAsyncTask`] as an event or by inserting it as a component.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// struct OpenDoor {
+///     frames: usize,
+/// }
+///
+/// #[async_trait]
+/// impl AsyncTask for OpenDoor {
+///     async fn run(self, schedules: AsyncSchedules) {
+///         schedules.add_system(Update, delay::frames(self.frames)).await;
+///         schedules.add_system(Update, once::run(|| println!("The door is open."))).await;
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     TaskPoolPlugin::default(),
+///     AsyncSystemPlugin::default()
+/// ));
+/// app.register_async_task::<OpenDoor>();
+/// app.add_systems(Update, |mut events: EventWriter<SpawnAsyncTask<OpenDoor>>| {
+///     events.send(SpawnAsyncTask(OpenDoor { frames: 30 }));
+/// });
+/// ```
+#[async_trait]
+pub trait AsyncTask: Send + Sync + 'static {
+    async fn run(self, schedules: AsyncSchedules);
+}
+
+
+/// Spawns the task of the registered [`AsyncTask`].
+///
+/// Sent as an event, a new task entity is spawned.
+/// Inserted as a component, the component is removed and the task entity is spawned as a child of the entity,
+/// so the task is cancelled when the entity is despawned recursively.
+///
+/// Both are handled in [`AsyncSystemPlugin::setup_schedule`](crate::AsyncSystemPlugin::setup_schedule),
+/// and the events are consumed there.
+#[derive(Event, Component, Debug, Clone)]
+pub struct SpawnAsyncTask<T: AsyncTask>(pub T);
+
+
+pub(crate) fn spawn_async_tasks<T: AsyncTask>(
+    mut commands: Commands,
+    mut events: ResMut<Events<SpawnAsyncTask<T>>>,
+    triggers: Query<Entity, Added<SpawnAsyncTask<T>>>,
+) {
+    for SpawnAsyncTask(task) in events.drain() {
+        commands.spawn(async_task_bundle(|schedules| task.run(schedules)));
+    }
+
+    for entity in triggers.iter() {
+        commands.add(move |world: &mut World| {
+            let Some(SpawnAsyncTask(task)) = world.get_entity_mut(entity).and_then(|mut entity| entity.take::<SpawnAsyncTask<T>>()) else { return; };
+            world
+                .spawn(async_task_bundle(|schedules| task.run(schedules)))
+                .set_parent(entity);
+        });
+    }
+}
@@ -0,0 +1,310 @@
+use std::fmt::{Debug, Formatter};
+
+use bevy::prelude::Resource;
+
+/// Creates a channel that holds at most `capacity` values.
+///
+/// Either end can be inserted as a [`Resource`] so that systems use it through [`Res`](bevy::prelude::Res),
+/// while the other end is moved into an async task.
+/// When the task entity is despawned, its future is dropped together with the end it owns,
+/// so the channel is closed once no other clone of that end is alive.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+/// use bevy_async_system::channel;
+///
+/// fn setup(mut commands: Commands) {
+///     let (tx, rx) = channel::bounded::<KeyCode>(8);
+///     commands.insert_resource(tx);
+///     commands.spawn_async(|schedules| async move {
+///         while let Some(key) = rx.recv().await {
+///             println!("{key:?} was pressed");
+///             schedules.add_system(Update, delay::frames(30)).await;
+///         }
+///     });
+/// }
+///
+/// fn forward_keys(tx: Res<AsyncSender<KeyCode>>, keys: Res<Input<KeyCode>>) {
+///     for key in keys.get_just_pressed() {
+///         let _ = tx.try_send(*key);
+///     }
+/// }
+/// ```
+///
+/// ## Panics
+///
+/// Panics if `capacity` is zero.
+#[inline]
+pub fn bounded<T>(capacity: usize) -> (AsyncSender<T>, AsyncReceiver<T>) {
+    assert!(0 < capacity, "a bounded channel must have a capacity of at least one");
+    let (tx, rx) = async_channel::bounded(capacity);
+    (AsyncSender(tx), AsyncReceiver(rx))
+}
+
+
+/// Creates a channel without a limit on the number of values it holds.
+///
+/// See [`bounded`] for how the channel is shared between systems and tasks.
+#[inline]
+pub fn unbounded<T>() -> (AsyncSender<T>, AsyncReceiver<T>) {
+    let (tx, rx) = async_channel::unbounded();
+    (AsyncSender(tx), AsyncReceiver(rx))
+}
+
+
+/// Why [`AsyncSender::try_send`] failed; the value is returned back.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum TrySendError<T> {
+    /// The bounded channel is full.
+    Full(T),
+
+    /// All receivers have been dropped or the channel has been closed.
+    Closed(T),
+}
+
+
+impl<T> TrySendError<T> {
+    /// Returns the value that could not be sent.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Full(value) | Self::Closed(value) => value
+        }
+    }
+}
+
+
+impl<T> Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("Full(..)"),
+            Self::Closed(_) => f.write_str("Closed(..)")
+        }
+    }
+}
+
+
+/// The sending end of a channel created by [`bounded`] or [`unbounded`].
+///
+/// It can be cloned; the channel is closed when every sender or every receiver is dropped.
+#[derive(Resource)]
+pub struct AsyncSender<T>(async_channel::Sender<T>);
+
+
+impl<T> AsyncSender<T> {
+    /// Sends the value without waiting, which is what systems should use.
+    #[inline]
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.0.try_send(value).map_err(|error| match error {
+            async_channel::TrySendError::Full(value) => TrySendError::Full(value),
+            async_channel::TrySendError::Closed(value) => TrySendError::Closed(value)
+        })
+    }
+
+
+    /// Sends the value, waiting while the bounded channel is full.
+    ///
+    /// Returns the value back if the channel is closed.
+    #[inline]
+    pub async fn send(&self, value: T) -> Result<(), T> {
+        self.0.send(value).await.map_err(|error| error.0)
+    }
+
+
+    /// Closes the channel; values already sent can still be received.
+    ///
+    /// Returns false if the channel was already closed.
+    #[inline]
+    pub fn close(&self) -> bool {
+        self.0.close()
+    }
+
+
+    /// Returns true if the channel is closed.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+
+    /// Returns the number of values in the channel.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+
+    /// Returns true if the channel holds no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+
+impl<T> Clone for AsyncSender<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+
+/// The receiving end of a channel created by [`bounded`] or [`unbounded`].
+///
+/// It can be cloned; each value is received by only one of the clones.
+#[derive(Resource)]
+pub struct AsyncReceiver<T>(async_channel::Receiver<T>);
+
+
+impl<T> AsyncReceiver<T> {
+    /// Receives a value without waiting, which is what systems should use.
+    #[inline]
+    pub fn try_recv(&self) -> Option<T> {
+        self.0.try_recv().ok()
+    }
+
+
+    /// Receives all values currently in the channel without waiting.
+    #[inline]
+    pub fn drain(&self) -> impl Iterator<Item=T> + '_ {
+        std::iter::from_fn(|| self.try_recv())
+    }
+
+
+    /// Waits for a value.
+    ///
+    /// Returns `None` once the channel is closed and empty.
+    #[inline]
+    pub async fn recv(&self) -> Option<T> {
+        self.0.recv().await.ok()
+    }
+
+
+    /// Closes the channel; values already sent can still be received.
+    ///
+    /// Returns false if the channel was already closed.
+    #[inline]
+    pub fn close(&self) -> bool {
+        self.0.close()
+    }
+
+
+    /// Returns true if the channel is closed.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+
+    /// Returns the number of values in the channel.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+
+    /// Returns true if the channel holds no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+
+impl<T> Clone for AsyncReceiver<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::prelude::{Local, Res, ResMut, Resource};
+
+    use crate::channel::{AsyncReceiver, AsyncSender, bounded, TrySendError, unbounded};
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::once;
+    use crate::test_util::new_deterministic_app;
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<u32>);
+
+
+    #[test]
+    fn send_from_system_to_task() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Received>();
+        let (tx, rx) = unbounded::<u32>();
+        app.insert_resource(tx);
+        app.add_systems(Update, |tx: Res<AsyncSender<u32>>, mut count: Local<u32>| {
+            *count += 1;
+            if *count <= 3 {
+                tx.try_send(*count).unwrap();
+            }
+        });
+        app.spawn_async(|schedules| async move {
+            while let Some(value) = rx.recv().await {
+                schedules.add_system(Update, once::run(move |mut received: ResMut<Received>| received.0.push(value))).await;
+            }
+        });
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Received>().0, vec![1, 2, 3]);
+    }
+
+
+    #[test]
+    fn send_from_task_to_system() {
+        let mut app = new_deterministic_app();
+        let (tx, rx) = bounded::<u32>(1);
+        app.insert_resource(rx);
+        app.spawn_async(|schedules| async move {
+            for value in 0..2 {
+                schedules.add_system(Update, once::run(|| {})).await;
+                tx.send(value).await.unwrap();
+            }
+        });
+
+        let mut received = Vec::new();
+        for _ in 0..5 {
+            app.update();
+            received.extend(app.world.resource::<AsyncReceiver<u32>>().drain());
+        }
+        assert_eq!(received, vec![0, 1]);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "a bounded channel must have a capacity of at least one")]
+    fn reject_zero_capacity() {
+        let _ = bounded::<u32>(0);
+    }
+
+
+    #[test]
+    fn close_when_task_despawned() {
+        let mut app = new_deterministic_app();
+        let (tx, rx) = bounded::<u32>(1);
+        let task = app.spawn_async(|schedules| async move {
+            loop {
+                schedules.add_system(Update, once::run(|| {})).await;
+                let _ = rx.try_recv();
+            }
+        }).id();
+
+        app.update();
+        assert_eq!(tx.try_send(1), Ok(()));
+        assert!(matches!(tx.try_send(2), Err(TrySendError::Full(2))));
+
+        app.world.despawn(task);
+        assert!(tx.is_closed());
+        assert!(matches!(tx.try_send(3), Err(TrySendError::Closed(3))));
+    }
+}
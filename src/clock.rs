@@ -0,0 +1,162 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::prelude::{Res, ResMut, Resource};
+use bevy::time::Time;
+
+/// The time source of [`delay::timer`](crate::runner::delay::timer).
+///
+/// By default it follows [`Time`], so the delays are affected by pausing or scaling the time.
+/// Replace the resource with [`AsyncClock::manual`] to control the elapsed time explicitly.
+///
+/// The elapsed time of the frame is taken in
+/// [`AsyncSystemPlugin::setup_schedule`](crate::AsyncSystemPlugin::setup_schedule),
+/// after [`Time`] has been updated.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AsyncClock {
+    source: ClockSource,
+    delta: Duration,
+}
+
+
+#[derive(Debug, Clone, Default)]
+enum ClockSource {
+    #[default]
+    Time,
+    Manual(ManualAsyncClock),
+}
+
+
+impl AsyncClock {
+    /// Creates the clock that follows [`Time`].
+    #[inline]
+    pub fn time() -> Self {
+        Self::default()
+    }
+
+
+    /// Creates the clock that only advances by [`ManualAsyncClock::advance`].
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    ///
+    /// #[derive(Event, Clone)]
+    /// struct Elapsed;
+    ///
+    /// let clock = ManualAsyncClock::default();
+    /// let mut app = App::new();
+    /// app.add_plugins((
+    ///     TaskPoolPlugin::default(),
+    ///     AsyncSystemPlugin {
+    ///         execution: TaskExecution::Deterministic,
+    ///         ..default()
+    ///     }
+    /// ));
+    /// app.add_event::<Elapsed>();
+    /// app.insert_resource(AsyncClock::manual(clock.clone()));
+    /// app.add_systems(Startup, |mut commands: Commands| {
+    ///     commands.spawn_async(|schedules| async move {
+    ///         schedules.add_system(Update, delay::timer(Duration::from_secs(30))).await;
+    ///         schedules.add_system(Update, once::send(Elapsed)).await;
+    ///     });
+    /// });
+    ///
+    /// app.update();
+    /// clock.advance(Duration::from_secs(30));
+    /// app.update();
+    /// app.update();
+    /// assert!(!app.world.resource::<Events<Elapsed>>().is_empty());
+    /// ```
+    #[inline]
+    pub fn manual(clock: ManualAsyncClock) -> Self {
+        Self {
+            source: ClockSource::Manual(clock),
+            delta: Duration::ZERO,
+        }
+    }
+
+
+    /// Returns the time elapsed in the current frame.
+    #[inline]
+    pub const fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+
+/// A handle to advance [`AsyncClock::manual`].
+///
+/// Clones share the same clock, so keep one in the test and pass another to the clock.
+#[derive(Debug, Clone, Default)]
+pub struct ManualAsyncClock(Arc<Mutex<Duration>>);
+
+
+impl ManualAsyncClock {
+    /// Advances the clock by `duration`.
+    ///
+    /// The time elapses in the next frame.
+    #[inline]
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+
+
+    #[inline]
+    fn take(&self) -> Duration {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+
+pub(crate) fn update_async_clock(
+    mut clock: ResMut<AsyncClock>,
+    time: Option<Res<Time>>,
+) {
+    clock.delta = match &clock.source {
+        ClockSource::Time => time.map(|time| time.delta()).unwrap_or_default(),
+        ClockSource::Manual(manual) => manual.take()
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::app::{Startup, Update};
+    use bevy::ecs::event::ManualEventReader;
+    use bevy::prelude::Commands;
+
+    use crate::clock::{AsyncClock, ManualAsyncClock};
+    use crate::ext::spawn_async_system::SpawnAsyncSystem;
+    use crate::runner::{delay, once};
+    use crate::test_util::{FirstEvent, is_first_event_already_coming, new_deterministic_app};
+
+    #[test]
+    fn delay_by_manual_clock() {
+        let clock = ManualAsyncClock::default();
+        let mut app = new_deterministic_app();
+        app.insert_resource(AsyncClock::manual(clock.clone()));
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system(Update, delay::timer(Duration::from_secs(30))).await;
+                schedules.add_system(Update, once::send(FirstEvent)).await;
+            });
+        });
+
+        let mut er = ManualEventReader::default();
+        app.update();
+        clock.advance(Duration::from_secs(29));
+        app.update();
+        app.update();
+        assert!(!is_first_event_already_coming(&mut app, &mut er));
+
+        clock.advance(Duration::from_secs(1));
+        app.update();
+        assert_eq!(app.world.resource::<AsyncClock>().delta(), Duration::from_secs(1));
+        app.update();
+        assert!(is_first_event_already_coming(&mut app, &mut er));
+    }
+}
@@ -0,0 +1,238 @@
+use std::fmt::{Display, Formatter};
+use std::panic::Location;
+use std::sync::Weak;
+
+use bevy::core::Name;
+use bevy::ecs::schedule::BoxedScheduleLabel;
+use bevy::log::info;
+use bevy::prelude::{Children, Component, Entity, Query, ResMut, Resource, With};
+
+use crate::async_schedules::TaskHandle;
+use crate::runner::AwaitingOutput;
+
+/// Describes a system added by [`AsyncSchedules::add_system`](crate::async_schedules::AsyncSchedules::add_system).
+///
+/// It is attached to each runner entity, that is, each child of the task entity.
+#[derive(Component, Debug, Clone)]
+pub struct AsyncRunnerInfo {
+    /// The kind of the runner, such as `once::run` or `wait::until`.
+    pub kind: &'static str,
+
+    /// The type name of the system, if the runner runs one.
+    pub system: Option<&'static str>,
+
+    /// The schedule the system was added to.
+    pub schedule: Option<BoxedScheduleLabel>,
+
+    /// The [`FrameCount`](bevy::core::FrameCount) when the system was registered,
+    /// if [`FrameCountPlugin`](bevy::core::FrameCountPlugin) is added.
+    pub started_frame: Option<u32>,
+
+    /// The location in the async body that added the system.
+    pub location: Option<&'static Location<'static>>,
+
+    pub(crate) awaiting: Weak<AwaitingOutput>,
+}
+
+
+impl AsyncRunnerInfo {
+    /// Returns true while the task is still waiting for the output of the system.
+    #[inline]
+    pub fn is_awaited(&self) -> bool {
+        0 < self.awaiting.strong_count()
+    }
+}
+
+
+impl Display for AsyncRunnerInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(system) = self.system {
+            write!(f, "({system})")?;
+        }
+        if let Some(schedule) = self.schedule.as_ref() {
+            write!(f, " in {schedule:?}")?;
+        }
+        if let Some(frame) = self.started_frame {
+            write!(f, " since frame {frame}")?;
+        }
+        if let Some(location) = self.location {
+            write!(f, " at {location}")?;
+        }
+        Ok(())
+    }
+}
+
+
+/// A snapshot of a running task.
+#[derive(Debug, Clone)]
+pub struct TaskDiagnostic {
+    /// The entity of the task.
+    pub task: Entity,
+
+    /// The [`Name`] of the task entity, if any.
+    pub name: Option<String>,
+
+    /// The systems the task is currently waiting for.
+    pub awaiting: Vec<AsyncRunnerInfo>,
+}
+
+
+/// Lists the running async tasks and the systems they are waiting for.
+///
+/// Collection is opt-in: the snapshot is refreshed every frame in
+/// [`AsyncSystemPlugin::cleanup_schedule`](crate::AsyncSystemPlugin::cleanup_schedule)
+/// only while this resource exists.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+/// use bevy_async_system::diagnostics::AsyncTaskDiagnostics;
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     TaskPoolPlugin::default(),
+///     AsyncSystemPlugin::default()
+/// ));
+/// app.init_resource::<AsyncTaskDiagnostics>();
+/// app.add_systems(Startup, |mut commands: Commands| {
+///     commands.spawn_async(|schedules| async move {
+///         schedules.add_system(Update, wait::until(|| false)).await;
+///     });
+/// });
+/// app.update();
+///
+/// let diagnostics = app.world.resource::<AsyncTaskDiagnostics>();
+/// assert_eq!(diagnostics.tasks()[0].awaiting[0].kind, "wait::until");
+/// diagnostics.log();
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct AsyncTaskDiagnostics {
+    tasks: Vec<TaskDiagnostic>,
+}
+
+
+impl AsyncTaskDiagnostics {
+    /// Returns the tasks that were running at the last refresh.
+    #[inline]
+    pub fn tasks(&self) -> &[TaskDiagnostic] {
+        &self.tasks
+    }
+
+
+    /// Returns the snapshot of the task, if it was running at the last refresh.
+    #[inline]
+    pub fn get(&self, task: Entity) -> Option<&TaskDiagnostic> {
+        self.tasks.iter().find(|diagnostic| diagnostic.task == task)
+    }
+
+
+    /// Dumps the snapshot to the log.
+    pub fn log(&self) {
+        info!("{} async task(s) running", self.tasks.len());
+        for diagnostic in self.tasks.iter() {
+            info!("task {:?} ({})", diagnostic.task, diagnostic.name.as_deref().unwrap_or("unnamed"));
+            for runner in diagnostic.awaiting.iter() {
+                info!("    awaiting {runner}");
+            }
+        }
+    }
+}
+
+
+pub(crate) fn update_task_diagnostics(
+    diagnostics: Option<ResMut<AsyncTaskDiagnostics>>,
+    tasks: Query<(Entity, Option<&Name>, Option<&Children>), With<TaskHandle>>,
+    runners: Query<&AsyncRunnerInfo>,
+) {
+    let Some(mut diagnostics) = diagnostics else { return; };
+
+    diagnostics.tasks = tasks
+        .iter()
+        .map(|(task, name, children)| TaskDiagnostic {
+            task,
+            name: name.map(|name| name.to_string()),
+            awaiting: children
+                .into_iter()
+                .flat_map(|children| runners.iter_many(children))
+                .filter(|runner| runner.is_awaited())
+                .cloned()
+                .collect(),
+        })
+        .collect();
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{Startup, Update};
+    use bevy::core::Name;
+    use bevy::prelude::{Commands, Resource};
+
+    use crate::diagnostics::AsyncTaskDiagnostics;
+    use crate::ext::spawn_async_system::SpawnAsyncSystem;
+    use crate::runner::{once, wait};
+    use crate::test_util::{FirstEvent, new_deterministic_app};
+
+    #[test]
+    fn list_awaited_systems() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<AsyncTaskDiagnostics>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands
+                .spawn_async(|schedules| async move {
+                    schedules.add_system(Update, once::run(|| {})).await;
+                    schedules.add_system(Update, wait::until(never)).await;
+                })
+                .insert(Name::new("cutscene"));
+        });
+
+        app.update();
+        let diagnostics = app.world.resource::<AsyncTaskDiagnostics>();
+        assert_eq!(diagnostics.tasks()[0].awaiting.len(), 1);
+        assert_eq!(diagnostics.tasks()[0].awaiting[0].kind, "once::run");
+
+        app.update();
+        app.update();
+        let diagnostics = app.world.resource::<AsyncTaskDiagnostics>();
+        assert_eq!(diagnostics.tasks().len(), 1);
+        let task = &diagnostics.tasks()[0];
+        assert_eq!(task.name.as_deref(), Some("cutscene"));
+        assert_eq!(task.awaiting.len(), 1);
+
+        let runner = &task.awaiting[0];
+        assert_eq!(runner.kind, "wait::until");
+        assert!(runner.system.unwrap().ends_with("never"));
+        assert_eq!(format!("{:?}", runner.schedule.as_ref().unwrap()), "Update");
+        assert_eq!(runner.started_frame, Some(1));
+        assert_eq!(runner.location.unwrap().file(), file!());
+    }
+
+
+    #[test]
+    fn describe_once_runners_by_constructor() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<AsyncTaskDiagnostics>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system(Update, once::send(FirstEvent)).await;
+                schedules.add_system(Update, once::init_resource::<Count>()).await;
+            });
+        });
+
+        app.update();
+        assert_eq!(app.world.resource::<AsyncTaskDiagnostics>().tasks()[0].awaiting[0].kind, "once::send");
+
+        app.update();
+        assert_eq!(app.world.resource::<AsyncTaskDiagnostics>().tasks()[0].awaiting[0].kind, "once::init_resource");
+    }
+
+
+    #[derive(Resource, Default)]
+    struct Count;
+
+
+    fn never() -> bool {
+        false
+    }
+}
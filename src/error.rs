@@ -0,0 +1,36 @@
+use std::fmt::{Display, Formatter};
+
+use bevy::ecs::schedule::BoxedScheduleLabel;
+use bevy::prelude::{Entity, Event};
+
+/// Errors reported while driving async tasks.
+///
+/// They are sent as events, so they can be read with [`EventReader`](bevy::prelude::EventReader).
+#[derive(Event, Debug, Clone, Eq, PartialEq)]
+pub enum AsyncTaskError {
+    /// A system was added to a schedule that does not exist.
+    ///
+    /// Only reported when [`AsyncSystemPlugin::strict_schedules`](crate::AsyncSystemPlugin::strict_schedules) is enabled.
+    /// The system is not registered, and the `await` panics with this error, which ends the task
+    /// or restarts it if it is supervised by a [`Supervisor`](crate::supervisor::Supervisor).
+    UnknownSchedule {
+        /// The entity of the task that added the system.
+        task: Entity,
+        /// The label of the missing schedule.
+        schedule: BoxedScheduleLabel,
+    },
+}
+
+
+impl Display for AsyncTaskError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownSchedule { task, schedule } => {
+                write!(f, "task {task:?} added a system to the unknown schedule {schedule:?}")
+            }
+        }
+    }
+}
+
+
+impl std::error::Error for AsyncTaskError {}
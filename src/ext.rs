@@ -1,2 +1,6 @@
 pub mod spawn_async_system;
+pub mod route_async_systems;
+pub mod add_async_schedule;
 
+pub mod async_task_commands;
+pub mod register_async_task;
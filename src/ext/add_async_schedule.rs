@@ -0,0 +1,50 @@
+use bevy::app::{App, MainScheduleOrder};
+use bevy::ecs::schedule::ScheduleLabel;
+
+pub trait AddAsyncSchedule {
+    /// Registers a custom schedule and runs it every frame right after the `after` schedule of [`MainScheduleOrder`].
+    ///
+    /// Register custom schedules explicitly when
+    /// [`AsyncSystemPlugin::strict_schedules`](crate::AsyncSystemPlugin::strict_schedules) is enabled,
+    /// because systems added to unknown schedules are rejected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use bevy::ecs::schedule::ScheduleLabel;
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    /// use bevy_async_system::ext::add_async_schedule::AddAsyncSchedule;
+    ///
+    /// #[derive(ScheduleLabel, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    /// struct Cutscene;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((
+    ///     TaskPoolPlugin::default(),
+    ///     AsyncSystemPlugin {
+    ///         strict_schedules: true,
+    ///         ..default()
+    ///     }
+    /// ));
+    /// app.add_async_schedule_after(Update, Cutscene);
+    /// app.add_systems(Startup, |mut commands: Commands| {
+    ///     commands.spawn_async(|schedules| async move {
+    ///         schedules.add_system(Cutscene, once::run(|| {})).await;
+    ///     });
+    /// });
+    /// app.update();
+    /// ```
+    fn add_async_schedule_after(&mut self, after: impl ScheduleLabel, schedule_label: impl ScheduleLabel + Clone) -> &mut Self;
+}
+
+
+impl AddAsyncSchedule for App {
+    fn add_async_schedule_after(&mut self, after: impl ScheduleLabel, schedule_label: impl ScheduleLabel + Clone) -> &mut Self {
+        self.init_schedule(schedule_label.clone());
+        self.world
+            .resource_mut::<MainScheduleOrder>()
+            .insert_after(after, schedule_label);
+        self
+    }
+}
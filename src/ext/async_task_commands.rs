@@ -0,0 +1,86 @@
+use bevy::hierarchy::despawn_with_children_recursive;
+use bevy::log::warn;
+use bevy::prelude::{Commands, Entity, World};
+
+use crate::async_schedules::PausedTask;
+use crate::registry::AsyncTaskRegistry;
+use crate::restart::RestartAsyncTask;
+
+/// Controls the tasks spawned by [`spawn_async_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_named)
+/// or [`spawn_async_restartable_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_restartable_named) by name.
+///
+/// The name is resolved through [`AsyncTaskRegistry`] when the command is applied.
+/// If no task with the name is running, a warning is logged and nothing happens.
+pub trait AsyncTaskCommands {
+    /// Despawns the task named `name` together with the systems it is awaiting.
+    fn cancel_async_task(&mut self, name: impl Into<String>);
+
+
+    /// Pauses the task named `name` by inserting [`PausedTask`].
+    fn pause_async_task(&mut self, name: impl Into<String>);
+
+
+    /// Resumes the task named `name` by removing [`PausedTask`].
+    fn resume_async_task(&mut self, name: impl Into<String>);
+
+
+    /// Restarts the task named `name` by sending [`RestartAsyncTask`].
+    ///
+    /// The task must have been spawned by
+    /// [`spawn_async_restartable_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_restartable_named).
+    fn restart_async_task(&mut self, name: impl Into<String>);
+}
+
+
+impl<'w, 's> AsyncTaskCommands for Commands<'w, 's> {
+    fn cancel_async_task(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.add(move |world: &mut World| {
+            if let Some(task) = find_task(world, &name) {
+                despawn_with_children_recursive(world, task);
+            }
+        });
+    }
+
+
+    fn pause_async_task(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.add(move |world: &mut World| {
+            if let Some(task) = find_task(world, &name) {
+                world.entity_mut(task).insert(PausedTask);
+            }
+        });
+    }
+
+
+    fn resume_async_task(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.add(move |world: &mut World| {
+            if let Some(task) = find_task(world, &name) {
+                world.entity_mut(task).remove::<PausedTask>();
+            }
+        });
+    }
+
+
+    fn restart_async_task(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.add(move |world: &mut World| {
+            if let Some(task) = find_task(world, &name) {
+                world.send_event(RestartAsyncTask(task));
+            }
+        });
+    }
+}
+
+
+fn find_task(world: &World, name: &str) -> Option<Entity> {
+    let task = world
+        .get_resource::<AsyncTaskRegistry>()
+        .and_then(|registry| registry.get(name))
+        .filter(|task| world.get_entity(*task).is_some());
+    if task.is_none() {
+        warn!("No async task named {name} is running.");
+    }
+    task
+}
@@ -0,0 +1,93 @@
+use bevy::app::App;
+use bevy::prelude::IntoSystemConfigs;
+
+use crate::async_task::{AsyncTask, spawn_async_tasks, SpawnAsyncTask};
+use crate::AsyncSystemSettings;
+use crate::registry::register_named_tasks;
+
+pub trait RegisterAsyncTask {
+    /// Allows the task `T` to be spawned by [`SpawnAsyncTask<T>`].
+    ///
+    /// [`AsyncSystemPlugin`](crate::AsyncSystemPlugin) must be added before calling this.
+    fn register_async_task<T: AsyncTask>(&mut self) -> &mut Self;
+}
+
+
+impl RegisterAsyncTask for App {
+    fn register_async_task<T: AsyncTask>(&mut self) -> &mut Self {
+        let setup_schedule = self
+            .world
+            .get_resource::<AsyncSystemSettings>()
+            .expect("AsyncSystemPlugin must be added before registering async tasks")
+            .setup_schedule
+            .clone();
+
+        self
+            .add_event::<SpawnAsyncTask<T>>()
+            .add_systems(setup_schedule, spawn_async_tasks::<T>.before(register_named_tasks))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use bevy::app::Update;
+    use bevy::prelude::{Children, ResMut, Resource};
+
+    use crate::async_schedules::{AsyncSchedules, TaskHandle};
+    use crate::async_task::{AsyncTask, SpawnAsyncTask};
+    use crate::ext::register_async_task::RegisterAsyncTask;
+    use crate::runner::once;
+    use crate::test_util::{new_app, new_deterministic_app};
+
+    #[derive(Resource, Default)]
+    struct Opened(Vec<u32>);
+
+
+    struct OpenDoor(u32);
+
+
+    #[async_trait]
+    impl AsyncTask for OpenDoor {
+        async fn run(self, schedules: AsyncSchedules) {
+            schedules.add_system(Update, once::run(move |mut opened: ResMut<Opened>| {
+                opened.0.push(self.0);
+            })).await;
+        }
+    }
+
+
+    #[test]
+    fn spawn_by_event() {
+        let mut app = new_app();
+        app.init_resource::<Opened>();
+        app.register_async_task::<OpenDoor>();
+
+        app.world.send_event(SpawnAsyncTask(OpenDoor(1)));
+        app.world.send_event(SpawnAsyncTask(OpenDoor(2)));
+        app.update();
+        let mut opened = app.world.resource::<Opened>().0.clone();
+        opened.sort();
+        assert_eq!(opened, vec![1, 2]);
+    }
+
+
+    #[test]
+    fn spawn_by_component() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Opened>();
+        app.register_async_task::<OpenDoor>();
+
+        let door = app.world.spawn(SpawnAsyncTask(OpenDoor(3))).id();
+        app.update();
+        assert_eq!(app.world.resource::<Opened>().0, vec![3]);
+        assert!(app.world.get::<SpawnAsyncTask<OpenDoor>>(door).is_none());
+        let task = app.world.get::<Children>(door).unwrap()[0];
+        assert!(app.world.get::<TaskHandle>(task).is_some());
+
+        app.update();
+        assert!(app.world.get_entity(task).is_none());
+        assert!(app.world.get_entity(door).is_some());
+    }
+}
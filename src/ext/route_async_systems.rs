@@ -0,0 +1,345 @@
+use std::sync::{Arc, Mutex, Weak};
+
+use bevy::app::{App, AppLabel, AppLabelId, Main, MainScheduleOrder};
+use bevy::core::FrameCount;
+use bevy::hierarchy::DespawnRecursiveExt;
+use bevy::log::error;
+use bevy::prelude::{apply_deferred, Commands, Component, Entity, IntoSystemConfigs, Query, Res, ResMut, Resource, Schedules};
+use bevy::utils::HashMap;
+
+use crate::clock::{AsyncClock, update_async_clock};
+use crate::error::AsyncTaskError;
+use crate::runner::AsyncScheduleCommand;
+use crate::{AsyncSystemPlugin, schedule_exists};
+
+/// Routes the systems added by [`AsyncSchedules::add_system_to`](crate::async_schedules::AsyncSchedules::add_system_to)
+/// from the world in which the task runs to the world that receives them.
+///
+/// It is inserted by [`AsyncSystemPlugin`](crate::AsyncSystemPlugin).
+/// To route systems to an independent [`App`], clone this resource and pass it to
+/// [`RouteAsyncSystems::receive_async_systems`].
+#[derive(Resource, Clone, Default)]
+pub struct AsyncWorldRouter {
+    commands: Arc<Mutex<HashMap<AppLabelId, Vec<RoutedCommand>>>>,
+    errors: Arc<Mutex<Vec<AsyncTaskError>>>,
+}
+
+
+impl AsyncWorldRouter {
+    #[inline]
+    pub(crate) fn push(&self, target: AppLabelId, command: RoutedCommand) {
+        self.commands.lock().unwrap().entry(target).or_default().push(command);
+    }
+
+
+    #[inline]
+    fn take(&self, target: AppLabelId) -> Vec<RoutedCommand> {
+        self.commands.lock().unwrap().remove(&target).unwrap_or_default()
+    }
+
+
+    /// Takes the errors reported by the receiving worlds, to be sent as events in the world in which the tasks run.
+    #[inline]
+    pub(crate) fn take_errors(&self) -> Vec<AsyncTaskError> {
+        std::mem::take(&mut *self.errors.lock().unwrap())
+    }
+}
+
+
+pub(crate) struct RoutedCommand {
+    command: AsyncScheduleCommand,
+    owner: Weak<Mutex<Vec<AsyncScheduleCommand>>>,
+    task: Entity,
+    strict_schedules: bool,
+}
+
+
+impl RoutedCommand {
+    /// `strict_schedules` is the setting of the world in which `task` runs;
+    /// the schedule is looked up in the world that receives the command.
+    #[inline]
+    pub(crate) const fn new(
+        command: AsyncScheduleCommand,
+        owner: Weak<Mutex<Vec<AsyncScheduleCommand>>>,
+        task: Entity,
+        strict_schedules: bool,
+    ) -> Self {
+        Self {
+            command,
+            owner,
+            task,
+            strict_schedules,
+        }
+    }
+}
+
+
+/// Attached to an entity that runs a system on behalf of a task living in another world.
+///
+/// The entity is despawned once the task is gone.
+#[derive(Component)]
+struct RoutedRunner(Weak<Mutex<Vec<AsyncScheduleCommand>>>);
+
+
+#[derive(Resource)]
+struct AsyncWorldReceiver {
+    router: AsyncWorldRouter,
+    label: AppLabelId,
+}
+
+
+pub trait RouteAsyncSystems {
+    /// Allows the async tasks of this app to await systems in the sub app labeled `label`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use bevy::app::{AppLabel, SubApp};
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    /// use bevy_async_system::ext::route_async_systems::RouteAsyncSystems;
+    ///
+    /// #[derive(AppLabel, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    /// struct SimulationApp;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((
+    ///     TaskPoolPlugin::default(),
+    ///     AsyncSystemPlugin::default()
+    /// ));
+    /// app.insert_sub_app(SimulationApp, SubApp::new(App::new(), |_, _| {}));
+    /// app.route_async_systems_to_sub_app(SimulationApp);
+    ///
+    /// app.add_systems(Startup, |mut commands: Commands| {
+    ///     commands.spawn_async(|schedules| async move {
+    ///         // Runs in `Update` of `SimulationApp`.
+    ///         schedules.add_system_to(SimulationApp, Update, once::run(|| {
+    ///             println!("Hello from the sub app!");
+    ///         })).await;
+    ///     });
+    /// });
+    /// ```
+    fn route_async_systems_to_sub_app(&mut self, label: impl AppLabel) -> &mut Self;
+
+
+    /// Makes this app run the systems routed to `label` by `router`.
+    ///
+    /// Use it to drive systems of an [`App`] that is not a sub app of the app in which the tasks run.
+    /// The systems are registered in the [`main_schedule_label`](App::main_schedule_label) of this app.
+    ///
+    /// Unless this app has [`AsyncSystemPlugin`] itself, [`AsyncClock`] is initialized here and updated every frame
+    /// before the routed systems run, so that [`delay::timer`](crate::runner::delay::timer) and other clock-driven runners work here as well.
+    fn receive_async_systems(&mut self, label: impl AppLabel, router: AsyncWorldRouter) -> &mut Self;
+}
+
+
+impl RouteAsyncSystems for App {
+    fn route_async_systems_to_sub_app(&mut self, label: impl AppLabel) -> &mut Self {
+        let label = label.as_label();
+        let router = self.world.get_resource_or_insert_with(AsyncWorldRouter::default).clone();
+        self.sub_app_mut(label).receive_async_systems(label, router);
+        self
+    }
+
+
+    fn receive_async_systems(&mut self, label: impl AppLabel, router: AsyncWorldRouter) -> &mut Self {
+        self.insert_resource(AsyncWorldReceiver {
+            router,
+            label: label.as_label(),
+        });
+        let main_schedule_label = self.main_schedule_label.clone();
+        if !self.is_plugin_added::<AsyncSystemPlugin>() {
+            self.init_resource::<AsyncClock>();
+            self.add_systems(main_schedule_label.clone(), update_async_clock
+                .before(despawn_orphaned_runners)
+                .before(Main::run_main));
+        }
+        self.add_systems(main_schedule_label, (despawn_orphaned_runners, init_routed_schedulers, apply_deferred)
+            .chain()
+            .before(Main::run_main))
+    }
+}
+
+
+fn init_routed_schedulers(
+    mut commands: Commands,
+    mut schedules: ResMut<Schedules>,
+    receiver: Res<AsyncWorldReceiver>,
+    order: Option<Res<MainScheduleOrder>>,
+    frame_count: Option<Res<FrameCount>>,
+) {
+    for routed in receiver.router.take(receiver.label) {
+        if let Some(schedule_label) = routed
+            .command
+            .schedule_label()
+            .filter(|label| routed.strict_schedules && !schedule_exists(&schedules, order.as_deref(), *label)) {
+            let error = AsyncTaskError::UnknownSchedule {
+                task: routed.task,
+                schedule: schedule_label.dyn_clone(),
+            };
+            error!("{error}");
+            receiver.router.errors.lock().unwrap().push(error.clone());
+            routed.command.reject(error);
+            continue;
+        }
+
+        let info = routed.command.runner_info(frame_count.as_deref().map(|frame| frame.0));
+        #[cfg(feature = "tracing")]
+        let info = (crate::trace::RunnerSpan::new(&info, None), info);
+        let mut entity_commands = commands.spawn((RoutedRunner(routed.owner), info));
+        routed.command.initialize(&mut entity_commands, &mut schedules);
+    }
+}
+
+
+fn despawn_orphaned_runners(
+    mut commands: Commands,
+    runners: Query<(Entity, &RoutedRunner)>,
+) {
+    for (entity, runner) in runners.iter() {
+        if runner.0.strong_count() == 0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::app::{App, AppLabel, FixedUpdate, Startup, SubApp, Update};
+    use bevy::core::TaskPoolPlugin;
+    use bevy::ecs::event::ManualEventReader;
+    use bevy::prelude::{Commands, Events, ResMut, Resource, Schedules};
+    use bevy::utils::default;
+
+    use crate::{AsyncSystemPlugin, TaskExecution};
+    use crate::clock::{AsyncClock, ManualAsyncClock};
+    use crate::error::AsyncTaskError;
+    use crate::ext::route_async_systems::{RoutedRunner, RouteAsyncSystems};
+    use crate::ext::spawn_async_system::{SpawnAsyncSystem, WorldSpawnAsyncSystem};
+    use crate::runner::{delay, once};
+    use crate::supervisor::{RestartPolicy, Supervisor};
+    use crate::test_util::{FirstEvent, is_first_event_already_coming, new_deterministic_app};
+
+    #[derive(AppLabel, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    struct Secondary;
+
+
+    #[derive(Resource, Default)]
+    struct Count(u32);
+
+
+    #[test]
+    fn await_system_in_sub_app() {
+        let mut app = new_deterministic_app();
+        let mut sub_app = App::new();
+        sub_app.init_resource::<Count>();
+        app.insert_sub_app(Secondary, SubApp::new(sub_app, |_, _| {}));
+        app.route_async_systems_to_sub_app(Secondary);
+
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system_to(Secondary, Update, once::run(|mut count: ResMut<Count>| {
+                    count.0 += 1;
+                })).await;
+                schedules.add_system(Update, once::send(FirstEvent)).await;
+            });
+        });
+
+        app.update();
+        assert_eq!(app.sub_app(Secondary).world.resource::<Count>().0, 1);
+
+        app.update();
+        assert!(is_first_event_already_coming(&mut app, &mut Default::default()));
+
+        app.update();
+        app.update();
+        assert_eq!(app.sub_app(Secondary).world.resource::<Count>().0, 1);
+        let sub_world = &mut app.sub_app_mut(Secondary).world;
+        assert_eq!(sub_world.query::<&RoutedRunner>().iter(sub_world).count(), 0);
+    }
+
+
+    #[test]
+    fn strict_schedules_reject_unknown_schedule_in_target_world() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AsyncSystemPlugin {
+                execution: TaskExecution::Deterministic,
+                strict_schedules: true,
+                ..default()
+            }
+        ));
+        app.init_schedule(FixedUpdate);
+        app.insert_sub_app(Secondary, SubApp::new(App::new(), |_, _| {}));
+        app.route_async_systems_to_sub_app(Secondary);
+        let task = app.world.spawn_async_restartable(|schedules| async move {
+            schedules.add_system_to(Secondary, FixedUpdate, once::run(|| {})).await;
+        })
+            .insert(Supervisor::new(RestartPolicy::Never))
+            .id();
+
+        app.update();
+        app.update();
+
+        let mut er = ManualEventReader::<AsyncTaskError>::default();
+        let errors: Vec<_> = er.iter(app.world.resource::<Events<AsyncTaskError>>()).cloned().collect();
+        assert_eq!(errors, vec![AsyncTaskError::UnknownSchedule {
+            task,
+            schedule: Box::new(FixedUpdate),
+        }]);
+        assert!(!app.sub_app(Secondary).world.resource::<Schedules>().contains(&FixedUpdate));
+    }
+
+
+    #[test]
+    fn await_timer_in_sub_app() {
+        let clock = ManualAsyncClock::default();
+        let mut app = new_deterministic_app();
+        let mut sub_app = App::new();
+        sub_app.insert_resource(AsyncClock::manual(clock.clone()));
+        app.insert_sub_app(Secondary, SubApp::new(sub_app, |_, _| {}));
+        app.route_async_systems_to_sub_app(Secondary);
+
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system_to(Secondary, Update, delay::timer(Duration::from_secs(3))).await;
+                schedules.add_system(Update, once::send(FirstEvent)).await;
+            });
+        });
+
+        let mut er = ManualEventReader::default();
+        app.update();
+        app.update();
+        assert!(!is_first_event_already_coming(&mut app, &mut er));
+
+        clock.advance(Duration::from_secs(3));
+        app.update();
+        app.update();
+        assert!(is_first_event_already_coming(&mut app, &mut er));
+    }
+
+
+    #[test]
+    fn insert_clock_into_receiving_app() {
+        let mut app = new_deterministic_app();
+        app.insert_sub_app(Secondary, SubApp::new(App::new(), |_, _| {}));
+        app.route_async_systems_to_sub_app(Secondary);
+
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system_to(Secondary, Update, delay::timer(Duration::ZERO)).await;
+                schedules.add_system(Update, once::send(FirstEvent)).await;
+            });
+        });
+
+        app.update();
+        assert!(app.sub_app(Secondary).world.contains_resource::<AsyncClock>());
+
+        app.update();
+        assert!(is_first_event_already_coming(&mut app, &mut ManualEventReader::default()));
+    }
+}
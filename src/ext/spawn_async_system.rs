@@ -1,11 +1,18 @@
+use std::borrow::Cow;
 use std::future::Future;
 use async_compat::CompatExt;
 use async_trait::async_trait;
 
+use bevy::app::App;
+use bevy::core::Name;
 use bevy::ecs::system::EntityCommands;
-use bevy::prelude::Commands;
+use bevy::ecs::world::EntityMut;
+use bevy::hierarchy::ChildBuilder;
+use bevy::prelude::{Bundle, Commands, World};
 use bevy::tasks::AsyncComputeTaskPool;
-use crate::async_schedules::{AsyncSchedules, TaskHandle};
+use crate::async_schedules::{AsyncSchedules, catch_panic, TaskHandle};
+use crate::registry::DuplicateTaskPolicy;
+use crate::restart::restartable_async_task_bundle;
 
 #[async_trait]
 pub trait SpawnAsyncSystem<'w, 's> {
@@ -31,7 +38,11 @@ pub trait SpawnAsyncSystem<'w, 's> {
     /// app.add_event::<TestEvent>();
     /// app.add_plugins((
     ///     TaskPoolPlugin::default(),
-    ///     AsyncSystemPlugin
+    ///     AsyncSystemPlugin {
+    ///         // Makes the number of frames required by each `await` reproducible.
+    ///         execution: TaskExecution::Deterministic,
+    ///         ..default()
+    ///     }
     /// ));
     ///
     /// app.add_systems(Startup, |mut commands: Commands|{
@@ -108,38 +119,390 @@ pub trait SpawnAsyncSystem<'w, 's> {
     ///     count.0 +=1;
     /// }
     /// ```
-    fn spawn_async<'a, F>(&'a mut self, f: impl Fn(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a>
+    fn spawn_async<'a, F>(&'a mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a>
         where F: Future<Output=()> + Send + 'static;
 
 
-    fn spawn_async_local<'a, F>(&'a mut self, f: impl Fn(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a>
+    fn spawn_async_local<'a, F>(&'a mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a>
         where F: Future<Output=()> + 'static;
+
+
+    /// Same as [`spawn_async`](SpawnAsyncSystem::spawn_async), but gives the task a [`Name`]
+    /// and registers it to [`AsyncTaskRegistry`](crate::registry::AsyncTaskRegistry),
+    /// so that it can be looked up and controlled by name with
+    /// [`AsyncTaskCommands`](crate::ext::async_task_commands::AsyncTaskCommands).
+    ///
+    /// If a task with the same name is still running, the new task is rejected by default.
+    /// Insert [`DuplicateTaskPolicy::Replace`] to replace the running task instead.
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    ///
+    /// fn play_intro(mut commands: Commands) {
+    ///     commands
+    ///         .spawn_async_named("intro_cutscene", |schedules| async move {
+    ///             schedules.add_system(Update, delay::frames(60)).await;
+    ///         })
+    ///         .insert(DuplicateTaskPolicy::Replace);
+    /// }
+    ///
+    /// fn skip_intro(mut commands: Commands) {
+    ///     commands.cancel_async_task("intro_cutscene");
+    /// }
+    /// ```
+    fn spawn_async_named<'a, F>(&'a mut self, name: impl Into<Cow<'static, str>>, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a>
+        where F: Future<Output=()> + Send + 'static;
+
+
+    /// Same as [`spawn_async`](SpawnAsyncSystem::spawn_async), but keeps `f`
+    /// so that the task can be started again from the beginning by sending [`RestartAsyncTask`](crate::restart::RestartAsyncTask).
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct Patrol(Entity);
+    ///
+    /// fn start_patrol(mut commands: Commands) {
+    ///     let task = commands.spawn_async_restartable(|schedules| async move {
+    ///         schedules.add_system(Update, delay::frames(120)).await;
+    ///         schedules.add_system(Update, once::run(|| println!("Arrived at the checkpoint."))).await;
+    ///     }).id();
+    ///     commands.insert_resource(Patrol(task));
+    /// }
+    ///
+    /// fn restart_patrol(patrol: Res<Patrol>, mut restart: EventWriter<RestartAsyncTask>) {
+    ///     restart.send(RestartAsyncTask(patrol.0));
+    /// }
+    /// ```
+    fn spawn_async_restartable<'a, F>(&'a mut self, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityCommands<'w, 's, 'a>
+        where F: Future<Output=()> + Send + 'static;
+
+
+    /// Same as [`spawn_async_restartable`](SpawnAsyncSystem::spawn_async_restartable), but names and registers the task
+    /// like [`spawn_async_named`](SpawnAsyncSystem::spawn_async_named).
+    ///
+    /// The task keeps its name across restarts, including those done by a [`Supervisor`](crate::supervisor::Supervisor).
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    ///
+    /// fn start_patrol(mut commands: Commands) {
+    ///     commands
+    ///         .spawn_async_restartable_named("patrol", |schedules| async move {
+    ///             schedules.add_system(Update, delay::frames(120)).await;
+    ///         })
+    ///         .insert(Supervisor::new(RestartPolicy::Always));
+    /// }
+    ///
+    /// fn stop_patrol(mut commands: Commands) {
+    ///     commands.cancel_async_task("patrol");
+    /// }
+    /// ```
+    fn spawn_async_restartable_named<'a, F>(&'a mut self, name: impl Into<Cow<'static, str>>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityCommands<'w, 's, 'a>
+        where F: Future<Output=()> + Send + 'static;
 }
 
 
 impl<'w, 's> SpawnAsyncSystem<'w, 's> for Commands<'w, 's> {
-    fn spawn_async<'a, F>(&'a mut self, f: impl Fn(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
-        let async_commands = AsyncSchedules::default();
-        let handle = AsyncComputeTaskPool::get().spawn(f(async_commands.clone()).compat());
+    fn spawn_async<'a, F>(&'a mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(async_task_bundle(f))
+    }
+
+
+    fn spawn_async_local<'a, F>(&'a mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + 'static {
+        self.spawn(local_async_task_bundle(f))
+    }
+
+
+    fn spawn_async_named<'a, F>(&'a mut self, name: impl Into<Cow<'static, str>>, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(named_async_task_bundle(name, f))
+    }
+
+
+    fn spawn_async_restartable<'a, F>(&'a mut self, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(restartable_async_task_bundle(None, f))
+    }
+
+
+    fn spawn_async_restartable_named<'a, F>(&'a mut self, name: impl Into<Cow<'static, str>>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(named_restartable_async_task_bundle(name, f))
+    }
+}
+
+
+/// Spawns the task as a child of the entity being built.
+impl<'w, 's, 'b> SpawnAsyncSystem<'w, 's> for ChildBuilder<'w, 's, 'b> {
+    fn spawn_async<'a, F>(&'a mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(async_task_bundle(f))
+    }
+
+
+    fn spawn_async_local<'a, F>(&'a mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + 'static {
+        self.spawn(local_async_task_bundle(f))
+    }
+
+
+    fn spawn_async_named<'a, F>(&'a mut self, name: impl Into<Cow<'static, str>>, f: impl FnOnce(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(named_async_task_bundle(name, f))
+    }
+
+
+    fn spawn_async_restartable<'a, F>(&'a mut self, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(restartable_async_task_bundle(None, f))
+    }
+
+
+    fn spawn_async_restartable_named<'a, F>(&'a mut self, name: impl Into<Cow<'static, str>>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + Send + 'static {
+        self.spawn(named_restartable_async_task_bundle(name, f))
+    }
+}
+
+
+/// The same as [`SpawnAsyncSystem`], but spawns the task into the [`World`] immediately.
+///
+/// It is implemented for [`World`] and [`App`], so plugins, exclusive systems and tests
+/// can start tasks without a system that takes [`Commands`].
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     TaskPoolPlugin::default(),
+///     AsyncSystemPlugin::default()
+/// ));
+/// app.spawn_async(|schedules| async move {
+///     schedules.add_system(Update, once::run(|| println!("Hello from the app!"))).await;
+/// });
+///
+/// app.update();
+/// ```
+pub trait WorldSpawnAsyncSystem {
+    /// See [`SpawnAsyncSystem::spawn_async`].
+    fn spawn_async<F>(&mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_>
+        where F: Future<Output=()> + Send + 'static;
+
+
+    /// See [`SpawnAsyncSystem::spawn_async_local`].
+    fn spawn_async_local<F>(&mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_>
+        where F: Future<Output=()> + 'static;
+
+
+    /// See [`SpawnAsyncSystem::spawn_async_named`].
+    fn spawn_async_named<F>(&mut self, name: impl Into<Cow<'static, str>>, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_>
+        where F: Future<Output=()> + Send + 'static;
+
+
+    /// See [`SpawnAsyncSystem::spawn_async_restartable`].
+    fn spawn_async_restartable<F>(&mut self, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityMut<'_>
+        where F: Future<Output=()> + Send + 'static;
+
+
+    /// See [`SpawnAsyncSystem::spawn_async_restartable_named`].
+    fn spawn_async_restartable_named<F>(&mut self, name: impl Into<Cow<'static, str>>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityMut<'_>
+        where F: Future<Output=()> + Send + 'static;
+}
+
+
+impl WorldSpawnAsyncSystem for World {
+    fn spawn_async<F>(&mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.spawn(async_task_bundle(f))
+    }
+
 
-        self.spawn((
-            async_commands.schedulers,
-            TaskHandle(handle)
-        ))
+    fn spawn_async_local<F>(&mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_> where F: Future<Output=()> + 'static {
+        self.spawn(local_async_task_bundle(f))
     }
 
 
-    fn spawn_async_local<'a, F>(&'a mut self, f: impl Fn(AsyncSchedules) -> F) -> EntityCommands<'w, 's, 'a> where F: Future<Output=()> + 'static {
-        let async_commands = AsyncSchedules::default();
-        let handle = AsyncComputeTaskPool::get().spawn_local(f(async_commands.clone()).compat());
+    fn spawn_async_named<F>(&mut self, name: impl Into<Cow<'static, str>>, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.spawn(named_async_task_bundle(name, f))
+    }
+
+
+    fn spawn_async_restartable<F>(&mut self, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.spawn(restartable_async_task_bundle(None, f))
+    }
+
 
-        self.spawn((
-            async_commands.schedulers,
-            TaskHandle(handle)
-        ))
+    fn spawn_async_restartable_named<F>(&mut self, name: impl Into<Cow<'static, str>>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.spawn(named_restartable_async_task_bundle(name, f))
     }
 }
 
 
+impl WorldSpawnAsyncSystem for App {
+    fn spawn_async<F>(&mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.world.spawn_async(f)
+    }
+
+
+    fn spawn_async_local<F>(&mut self, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_> where F: Future<Output=()> + 'static {
+        self.world.spawn_async_local(f)
+    }
+
+
+    fn spawn_async_named<F>(&mut self, name: impl Into<Cow<'static, str>>, f: impl FnOnce(AsyncSchedules) -> F) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.world.spawn_async_named(name, f)
+    }
 
 
+    fn spawn_async_restartable<F>(&mut self, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.world.spawn_async_restartable(f)
+    }
+
+
+    fn spawn_async_restartable_named<F>(&mut self, name: impl Into<Cow<'static, str>>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> EntityMut<'_> where F: Future<Output=()> + Send + 'static {
+        self.world.spawn_async_restartable_named(name, f)
+    }
+}
+
+
+/// Creates the components of a task entity that runs the future returned by `f`.
+#[inline]
+pub(crate) fn async_task_bundle<F>(f: impl FnOnce(AsyncSchedules) -> F) -> impl Bundle
+    where F: Future<Output=()> + Send + 'static
+{
+    async_task_bundle_with_name(None, f)
+}
+
+
+/// Same as [`async_task_bundle`], but names the span of the task when tracing is enabled.
+pub(crate) fn async_task_bundle_with_name<F>(name: Option<&str>, f: impl FnOnce(AsyncSchedules) -> F) -> impl Bundle
+    where F: Future<Output=()> + Send + 'static
+{
+    let async_commands = AsyncSchedules::default();
+    let future = f(async_commands.clone()).compat();
+    #[cfg(feature = "tracing")]
+    let (future, span) = instrument(future, name);
+    #[cfg(not(feature = "tracing"))]
+    let _ = name;
+
+    (
+        async_commands.schedulers,
+        TaskHandle::inline(future),
+        #[cfg(feature = "tracing")]
+        span
+    )
+}
+
+
+fn local_async_task_bundle<F>(f: impl FnOnce(AsyncSchedules) -> F) -> impl Bundle
+    where F: Future<Output=()> + 'static
+{
+    let async_commands = AsyncSchedules::default();
+    let future = f(async_commands.clone()).compat();
+    #[cfg(feature = "tracing")]
+    let (future, span) = instrument(future, None);
+
+    (
+        async_commands.schedulers,
+        TaskHandle::spawned(AsyncComputeTaskPool::get().spawn_local(catch_panic(future))),
+        #[cfg(feature = "tracing")]
+        span
+    )
+}
+
+
+fn named_async_task_bundle<F>(name: impl Into<Cow<'static, str>>, f: impl FnOnce(AsyncSchedules) -> F) -> impl Bundle
+    where F: Future<Output=()> + Send + 'static
+{
+    let name = Name::new(name);
+    (
+        async_task_bundle_with_name(Some(name.as_str()), f),
+        name,
+        DuplicateTaskPolicy::default()
+    )
+}
+
+
+fn named_restartable_async_task_bundle<F>(name: impl Into<Cow<'static, str>>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> impl Bundle
+    where F: Future<Output=()> + Send + 'static
+{
+    let name = Name::new(name);
+    (
+        restartable_async_task_bundle(Some(name.as_str()), f),
+        name,
+        DuplicateTaskPolicy::default()
+    )
+}
+
+
+#[cfg(feature = "tracing")]
+#[inline]
+fn instrument<F: Future>(future: F, name: Option<&str>) -> (bevy::utils::tracing::instrument::Instrumented<F>, crate::trace::TaskSpan) {
+    use bevy::utils::tracing::Instrument;
+
+    let span = crate::trace::TaskSpan::new(name);
+    (future.instrument(span.0.clone()), span)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{Startup, Update};
+    use bevy::hierarchy::{BuildChildren, Parent};
+    use bevy::prelude::Commands;
+
+    use crate::async_schedules::TaskHandle;
+    use crate::ext::spawn_async_system::{SpawnAsyncSystem, WorldSpawnAsyncSystem};
+    use crate::runner::once;
+    use crate::test_util::{FirstEvent, is_first_event_already_coming, new_deterministic_app};
+
+    #[test]
+    fn spawn_from_world() {
+        let mut app = new_deterministic_app();
+        let task = app.world.spawn_async(|schedules| async move {
+            schedules.add_system(Update, once::send(FirstEvent)).await;
+        }).id();
+        assert!(app.world.get::<TaskHandle>(task).is_some());
+
+        app.update();
+        assert!(is_first_event_already_coming(&mut app, &mut Default::default()));
+        app.update();
+        assert!(app.world.get_entity(task).is_none());
+    }
+
+
+    #[test]
+    fn move_captured_receiver_into_task() {
+        let mut app = new_deterministic_app();
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let task = app.world.spawn_async(move |schedules| async move {
+            let event = rx.await.unwrap();
+            schedules.add_system(Update, once::send(event)).await;
+        }).id();
+
+        app.update();
+        tx.send(FirstEvent).unwrap();
+        app.update();
+        app.update();
+        assert!(is_first_event_already_coming(&mut app, &mut Default::default()));
+        app.update();
+        assert!(app.world.get_entity(task).is_none());
+    }
+
+
+    #[test]
+    fn spawn_as_child() {
+        let mut app = new_deterministic_app();
+        let parent = app.world.spawn_empty().id();
+        app.add_systems(Startup, move |mut commands: Commands| {
+            commands.entity(parent).with_children(|parent| {
+                parent.spawn_async(|schedules| async move {
+                    schedules.add_system(Update, once::send(FirstEvent)).await;
+                });
+            });
+        });
+
+        app.update();
+        let mut tasks = app.world.query::<(&TaskHandle, &Parent)>();
+        assert!(tasks.iter(&app.world).all(|(_, task_parent)| task_parent.get() == parent));
+        assert_eq!(tasks.iter(&app.world).count(), 1);
+    }
+}
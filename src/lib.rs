@@ -1,66 +1,690 @@
-#![allow(clippy::type_complexity)]
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
 
-use bevy::app::{App, First, Main, Plugin};
-use bevy::hierarchy::DespawnRecursiveExt;
-use bevy::prelude::{Commands, Entity, Query, ResMut, Schedules};
-use futures_lite::future::block_on;
+use bevy::app::{App, First, Main, MainScheduleOrder, Plugin};
+use bevy::core::FrameCount;
+use bevy::ecs::schedule::{BoxedScheduleLabel, ScheduleLabel, SystemSet};
+use bevy::hierarchy::BuildChildren;
+use bevy::log::{error, warn};
+use bevy::prelude::{apply_deferred, Commands, Entity, EventWriter, IntoSystemConfigs, IntoSystemSetConfig, IntoSystemSetConfigs, Local, Query, Res, ResMut, Resource, Schedules, Without};
+use bevy::time::TimeSystem;
+use bevy::utils::HashSet;
 
-use crate::async_schedules::TaskHandle;
+use crate::async_schedules::{PausedTask, TaskHandle};
+use crate::clock::{AsyncClock, update_async_clock};
+use crate::diagnostics::update_task_diagnostics;
+use crate::error::AsyncTaskError;
+use crate::ext::route_async_systems::{AsyncWorldRouter, RoutedCommand};
+use crate::registry::{AsyncTaskRegistry, register_named_tasks};
+use crate::restart::{restart_async_tasks, RestartableTask, RestartAsyncTask};
+use crate::supervisor::{finish_task, SupervisedTaskGaveUp, Supervisor, tick_pending_restarts};
+use crate::sync::AsyncLocks;
+use crate::task_group::update_task_groups;
 use crate::runner::AsyncScheduleCommands;
 
 pub mod async_schedules;
+pub mod async_task;
+pub mod channel;
+pub mod clock;
+pub mod diagnostics;
+pub mod error;
 pub mod ext;
-
+pub mod registry;
+pub mod restart;
 pub mod runner;
+#[cfg(feature = "script")]
+pub mod script;
+pub mod supervisor;
+pub mod sync;
+pub mod task_group;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "tracing")]
+pub mod trace;
+
+#[cfg(feature = "macros")]
+pub use bevy_async_system_macros::async_system;
+
+// Lets the code generated by the macros refer to this crate as `::bevy_async_system`, even inside this crate.
+#[cfg(feature = "macros")]
+extern crate self as bevy_async_system;
+
+
+#[doc(hidden)]
+pub mod __private {
+    pub use async_trait::async_trait;
+    pub use bevy::ecs::system::{Commands, EntityCommands};
+}
 
 
 pub mod prelude {
     pub use crate::{
         async_schedules::*,
+        async_task::{AsyncTask, SpawnAsyncTask},
+        channel::{AsyncReceiver, AsyncSender},
+        clock::{AsyncClock, ManualAsyncClock},
+        error::AsyncTaskError,
+        AsyncCleanupSet,
         AsyncSystemPlugin,
-        ext::spawn_async_system::SpawnAsyncSystem,
+        FlushAsyncSchedules,
+        TaskExecution,
+        ext::async_task_commands::AsyncTaskCommands,
+        ext::register_async_task::RegisterAsyncTask,
+        ext::spawn_async_system::{SpawnAsyncSystem, WorldSpawnAsyncSystem},
+        registry::{AsyncTaskRegistry, DuplicateTaskPolicy},
+        restart::RestartAsyncTask,
+        supervisor::{Backoff, RestartPolicy, SupervisedTaskGaveUp, Supervisor},
+        sync::{AsyncLocks, AsyncSemaphore, AsyncSemaphorePermit},
+        task_group::{AsyncTaskGroup, TaskGroupMembers},
         runner::preludes::*,
     };
+
+    pub use async_trait::async_trait;
+
+    #[cfg(feature = "macros")]
+    pub use crate::async_system;
+}
+
+
+/// Determines how the futures of async tasks are driven.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TaskExecution {
+    /// Each task is spawned on [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool).
+    ///
+    /// The first poll is done in the setup schedule so that the first system is registered in the frame the task was spawned,
+    /// but the frame in which the code after each `await` runs depends on the timing of the task pool.
+    #[default]
+    Parallel,
+
+    /// All tasks are polled synchronously on the main thread in the setup schedule.
+    ///
+    /// The code after an `await` always runs in the setup schedule of the frame following the one
+    /// in which the awaited system finished, so the number of frames a task takes is reproducible.
+    /// This is useful for tests and replays.
+    ///
+    /// Tasks spawned by [`spawn_async_local`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_local)
+    /// are not [`Send`] and are always driven by the task pool.
+    Deterministic,
 }
 
+
+/// The schedule inserted between each schedule of [`MainScheduleOrder`]
+/// when [`AsyncSystemPlugin::flush_between_schedules`] is enabled.
+#[derive(ScheduleLabel, Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FlushAsyncSchedules;
+
+
+/// The systems of [`AsyncSystemPlugin`] in [`cleanup_schedule`](AsyncSystemPlugin::cleanup_schedule), in this order.
+///
+/// If the cleanup schedule is [`Main`], both sets run after the main schedules.
+#[derive(SystemSet, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AsyncCleanupSet {
+    /// Despawns the finished tasks when [`TaskExecution::Parallel`] is used.
+    RemoveFinishedTasks,
+
+    /// Refreshes [`AsyncTaskDiagnostics`](crate::diagnostics::AsyncTaskDiagnostics),
+    /// [`TaskGroupMembers`](crate::task_group::TaskGroupMembers) and the tracing spans.
+    Refresh,
+}
+
+
 /// Provides the async systems.
-pub struct AsyncSystemPlugin;
+///
+/// ## Ordering
+///
+/// Every frame the plugin does the following.
+///
+/// 1. In [`setup_schedule`](AsyncSystemPlugin::setup_schedule), newly spawned named tasks are registered to
+///    [`AsyncTaskRegistry`](crate::registry::AsyncTaskRegistry), then newly spawned tasks are polled for the first time
+///    (with [`TaskExecution::Deterministic`], every task that is not paused is polled and finished tasks are despawned),
+///    then the systems added by [`AsyncSchedules::add_system`](crate::async_schedules::AsyncSchedules::add_system)
+///    since the previous setup are registered to their schedules.
+/// 2. The registered systems run in their own schedules.
+///    A system is never registered to a schedule after that schedule has already run in the current frame,
+///    unless [`flush_between_schedules`](AsyncSystemPlugin::flush_between_schedules) is enabled.
+/// 3. With [`TaskExecution::Parallel`], finished tasks are despawned in
+///    [`cleanup_schedule`](AsyncSystemPlugin::cleanup_schedule).
+///    See [`AsyncCleanupSet`] for the order of the systems in that schedule.
+///
+/// Systems must not be awaited in the setup schedule itself,
+/// because the schedule is removed from [`Schedules`] while it runs.
+#[derive(Debug, Clone)]
+pub struct AsyncSystemPlugin {
+    /// How the futures of async tasks are driven.
+    pub execution: TaskExecution,
+
+    /// The schedule in which tasks are started and new systems are registered.
+    ///
+    /// Defaults to [`First`].
+    pub setup_schedule: BoxedScheduleLabel,
+
+    /// The schedule in which finished tasks are despawned when [`TaskExecution::Parallel`] is used,
+    /// and in which [`AsyncTaskDiagnostics`](crate::diagnostics::AsyncTaskDiagnostics) and [`TaskGroupMembers`](crate::task_group::TaskGroupMembers) are refreshed.
+    ///
+    /// Defaults to [`Main`].
+    pub cleanup_schedule: BoxedScheduleLabel,
+
+    /// If true, newly added systems are registered after every schedule of [`MainScheduleOrder`],
+    /// not only in the setup schedule.
+    ///
+    /// Combined with [`TaskExecution::Deterministic`], tasks are also polled at these points,
+    /// so a sequence of `await`s targeting schedules that run later in the frame
+    /// (e.g. `PreUpdate` -> `Update` -> `PostUpdate`) completes within a single frame.
+    ///
+    /// Only the schedules registered in [`MainScheduleOrder`] at the time this plugin is added are followed by a flush.
+    pub flush_between_schedules: bool,
+
+    /// If true, systems added to a schedule that does not exist are rejected
+    /// and [`AsyncTaskError::UnknownSchedule`] is sent; the task awaiting the system panics with the error.
+    ///
+    /// Otherwise an empty schedule is created and a warning is logged once per schedule.
+    /// Note that a created schedule only runs if something runs it;
+    /// custom schedules can be registered with [`AddAsyncSchedule`](crate::ext::add_async_schedule::AddAsyncSchedule).
+    ///
+    /// The schedules of [`MainScheduleOrder`] are always known.
+    /// Other schedules, such as `FixedUpdate`, are known only after they are created,
+    /// for example by adding a system or calling [`App::init_schedule`].
+    /// Systems routed by [`add_system_to`](crate::async_schedules::AsyncSchedules::add_system_to)
+    /// are checked against the schedules of the world that receives them.
+    pub strict_schedules: bool,
+}
+
+
+impl Default for AsyncSystemPlugin {
+    fn default() -> Self {
+        Self {
+            execution: TaskExecution::default(),
+            setup_schedule: Box::new(First),
+            cleanup_schedule: Box::new(Main),
+            flush_between_schedules: false,
+            strict_schedules: false,
+        }
+    }
+}
 
 
 impl Plugin for AsyncSystemPlugin {
     fn build(&self, app: &mut App) {
-        {
-            use bevy::prelude::IntoSystemConfigs;
-            app
-                .add_systems(Main, remove_finished_tasks)
-                .add_systems(First, init_async_schedulers.after(remove_finished_tasks));
+        app
+            .add_event::<AsyncTaskError>()
+            .add_event::<RestartAsyncTask>()
+            .add_event::<SupervisedTaskGaveUp>()
+            .init_resource::<AsyncWorldRouter>()
+            .init_resource::<AsyncTaskRegistry>()
+            .init_resource::<AsyncClock>()
+            .init_resource::<AsyncLocks>()
+            .insert_resource(AsyncSystemSettings {
+                setup_schedule: self.setup_schedule.clone(),
+                strict_schedules: self.strict_schedules,
+            });
+        app.configure_sets(self.cleanup_schedule.clone(), (AsyncCleanupSet::RemoveFinishedTasks, AsyncCleanupSet::Refresh).chain());
+        if self.cleanup_schedule.as_ref() == &Main as &dyn ScheduleLabel {
+            app.configure_set(Main, AsyncCleanupSet::RemoveFinishedTasks.after(Main::run_main));
+        }
+        app.add_systems(self.cleanup_schedule.clone(), (
+            update_task_diagnostics,
+            update_task_groups
+        ).in_set(AsyncCleanupSet::Refresh));
+        #[cfg(feature = "script")]
+        script::add_script_events(app);
+        #[cfg(feature = "tracing")]
+        app.add_systems(self.cleanup_schedule.clone(), trace::update_spans.in_set(AsyncCleanupSet::Refresh));
+        if self.execution == TaskExecution::Parallel {
+            // The despawns are applied before the refresh, so that it does not list finished tasks.
+            app.add_systems(self.cleanup_schedule.clone(), (remove_spawned_tasks, apply_deferred)
+                .chain()
+                .in_set(AsyncCleanupSet::RemoveFinishedTasks));
+        }
+        app.add_systems(self.setup_schedule.clone(), (
+            update_async_clock.after(TimeSystem),
+            restart_async_tasks.before(register_named_tasks),
+            tick_pending_restarts.after(update_async_clock).before(register_named_tasks)
+        ));
+        self.add_task_drivers(app, self.setup_schedule.clone());
+
+        if self.flush_between_schedules {
+            if let Some(mut order) = app.world.get_resource_mut::<MainScheduleOrder>() {
+                let labels = order.labels.clone();
+                for label in labels {
+                    order.insert_after(label, FlushAsyncSchedules);
+                }
+            }
+            self.add_task_drivers(app, FlushAsyncSchedules);
         }
     }
 }
 
+
+impl AsyncSystemPlugin {
+    fn add_task_drivers(&self, app: &mut App, schedule_label: impl ScheduleLabel) {
+        match self.execution {
+            TaskExecution::Parallel => {
+                app.add_systems(schedule_label, (register_named_tasks, apply_deferred, start_async_tasks, init_async_schedulers).chain());
+            }
+            TaskExecution::Deterministic => {
+                app.add_systems(schedule_label, (register_named_tasks, apply_deferred, remove_finished_tasks, init_async_schedulers).chain());
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct AsyncSystemSettings {
+    pub(crate) setup_schedule: BoxedScheduleLabel,
+    strict_schedules: bool,
+}
+
+
+impl AsyncSystemSettings {
+    /// The setup schedule is removed from [`Schedules`] while it runs.
+    fn schedule_exists(
+        &self,
+        schedules: &Schedules,
+        order: Option<&MainScheduleOrder>,
+        schedule_label: &dyn ScheduleLabel,
+    ) -> bool {
+        &*self.setup_schedule == schedule_label || schedule_exists(schedules, order, schedule_label)
+    }
+}
+
+
+/// Schedules listed in [`MainScheduleOrder`] are run even if they have not been created yet.
+pub(crate) fn schedule_exists(
+    schedules: &Schedules,
+    order: Option<&MainScheduleOrder>,
+    schedule_label: &dyn ScheduleLabel,
+) -> bool {
+    schedules.contains(schedule_label)
+        || order.is_some_and(|order| order.labels.iter().any(|label| &**label == schedule_label))
+}
+
+
 fn init_async_schedulers(
     mut commands: Commands,
     mut schedules: ResMut<Schedules>,
+    mut errors: EventWriter<AsyncTaskError>,
+    mut warned: Local<HashSet<BoxedScheduleLabel>>,
+    router: Res<AsyncWorldRouter>,
+    settings: Res<AsyncSystemSettings>,
+    order: Option<Res<MainScheduleOrder>>,
+    frame_count: Option<Res<FrameCount>>,
     executors_query: Query<(Entity, &AsyncScheduleCommands)>,
+    #[cfg(feature = "tracing")]
+    task_spans: Query<&trace::TaskSpan>,
 ) {
+    for error in router.take_errors() {
+        errors.send(error);
+    }
+
     for (entity, executors) in executors_query.iter() {
-        executors.init_schedulers(&mut commands.entity(entity), &mut schedules);
+        for command in executors.take() {
+            if let Some(target) = command.target() {
+                router.push(target, RoutedCommand::new(command, executors.downgrade(), entity, settings.strict_schedules));
+                continue;
+            }
+
+            if let Some(schedule_label) = command
+                .schedule_label()
+                .filter(|label| !settings.schedule_exists(&schedules, order.as_deref(), *label)) {
+                if settings.strict_schedules {
+                    let error = AsyncTaskError::UnknownSchedule {
+                        task: entity,
+                        schedule: schedule_label.dyn_clone(),
+                    };
+                    error!("{error}");
+                    errors.send(error.clone());
+                    command.reject(error);
+                    continue;
+                }
+                if warned.insert(schedule_label.dyn_clone()) {
+                    warn!("The schedule {schedule_label:?} does not exist, so an empty schedule is created. It will not run unless something runs it.");
+                }
+            }
+
+            let info = command.runner_info(frame_count.as_deref().map(|frame| frame.0));
+            #[cfg(feature = "tracing")]
+            let info = (trace::RunnerSpan::new(&info, task_spans.get(entity).ok()), info);
+            let runner = commands.spawn(info).id();
+            commands.entity(entity).add_child(runner);
+            command.initialize(&mut commands.entity(runner), &mut schedules);
+        }
     }
 }
 
 
-fn remove_finished_tasks(
+/// Polls newly spawned tasks once on the main thread, then hands them over to the task pool.
+fn start_async_tasks(
     mut commands: Commands,
     mut task_handles: Query<(Entity, &mut TaskHandle)>,
+    mut supervisors: Query<(&mut Supervisor, Option<&RestartableTask>)>,
+    mut gave_up: EventWriter<SupervisedTaskGaveUp>,
+) {
+    for (entity, mut task) in task_handles.iter_mut().filter(|(_, task)| task.is_inline()) {
+        match task.poll() {
+            Some(outcome) => finish_task(&mut commands, entity, outcome, &mut supervisors, &mut gave_up),
+            None => task.spawn_on_pool()
+        }
+    }
+}
+
+
+/// Polls only the tasks on the task pool; the others have not been started by [`start_async_tasks`] yet.
+fn remove_spawned_tasks(
+    mut commands: Commands,
+    mut task_handles: Query<(Entity, &mut TaskHandle)>,
+    mut supervisors: Query<(&mut Supervisor, Option<&RestartableTask>)>,
+    mut gave_up: EventWriter<SupervisedTaskGaveUp>,
+) {
+    for (entity, mut task) in task_handles.iter_mut().filter(|(_, task)| !task.is_inline()) {
+        if let Some(outcome) = task.poll() {
+            finish_task(&mut commands, entity, outcome, &mut supervisors, &mut gave_up);
+        }
+    }
+}
+
+
+fn remove_finished_tasks(
+    mut commands: Commands,
+    mut task_handles: Query<(Entity, &mut TaskHandle), Without<PausedTask>>,
+    mut supervisors: Query<(&mut Supervisor, Option<&RestartableTask>)>,
+    mut gave_up: EventWriter<SupervisedTaskGaveUp>,
 ) {
     for (entity, mut task) in task_handles.iter_mut() {
-        if block_on(futures_lite::future::poll_once(&mut task.0)).is_some() {
-            commands.entity(entity).despawn_recursive();
+        if let Some(outcome) = task.poll() {
+            finish_task(&mut commands, entity, outcome, &mut supervisors, &mut gave_up);
         }
     }
 }
 
 
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, First, FixedUpdate, Last, MainScheduleOrder, PostUpdate, PreUpdate, Startup, Update};
+    use bevy::core::{FrameCount, FrameCountPlugin, TaskPoolPlugin};
+    use bevy::ecs::event::ManualEventReader;
+    use bevy::ecs::schedule::ScheduleLabel;
+    use bevy::prelude::{Commands, Entity, Events, Res, ResMut, Resource, Schedules, With};
+    use bevy::utils::default;
+
+    use crate::{AsyncSystemPlugin, TaskExecution};
+    use crate::async_schedules::{TaskHandle, TaskOutcome};
+    use crate::diagnostics::AsyncTaskDiagnostics;
+    use crate::error::AsyncTaskError;
+    use crate::ext::spawn_async_system::{SpawnAsyncSystem, WorldSpawnAsyncSystem};
+    use crate::runner::once;
+    use crate::supervisor::{RestartPolicy, SupervisedTaskGaveUp, Supervisor};
+    use crate::test_util::{new_app, new_deterministic_app};
+
+    #[test]
+    fn deterministic_continuation_runs_in_next_frame() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Frames>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                for _ in 0..10 {
+                    schedules.add_system(Update, once::run(push_frame)).await;
+                }
+            });
+        });
+
+        for _ in 0..12 {
+            app.update();
+        }
+
+        assert_eq!(app.world.resource::<Frames>().0, (0..10).collect::<Vec<u32>>());
+    }
+
+
+    #[test]
+    fn parallel_task_finishes() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            FrameCountPlugin,
+            AsyncSystemPlugin::default()
+        ));
+        app.init_resource::<Frames>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                for _ in 0..3 {
+                    schedules.add_system(Update, once::run(push_frame)).await;
+                }
+            });
+        });
+
+        for _ in 0..1000 {
+            app.update();
+            if app.world.query_filtered::<(), With<TaskHandle>>().iter(&app.world).next().is_none() {
+                break;
+            }
+            std::thread::yield_now();
+        }
+
+        assert_eq!(app.world.resource::<Frames>().0.len(), 3);
+        assert_eq!(app.world.resource::<Frames>().0[0], 0);
+    }
+
+
+    #[test]
+    fn refresh_after_removing_tasks_in_custom_cleanup_schedule() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AsyncSystemPlugin {
+                cleanup_schedule: Box::new(Last),
+                ..default()
+            }
+        ));
+        app.init_resource::<AsyncTaskDiagnostics>();
+        let task = app.world.spawn_async(|schedules| async move {
+            schedules.add_system(Update, once::run(|| {})).await;
+        }).id();
+
+        for _ in 0..1000 {
+            app.update();
+            if app.world.get_entity(task).is_none() {
+                break;
+            }
+            assert!(app.world.resource::<AsyncTaskDiagnostics>().get(task).is_some());
+            std::thread::yield_now();
+        }
+        assert!(app.world.get_entity(task).is_none());
+        assert!(app.world.resource::<AsyncTaskDiagnostics>().tasks().is_empty());
+    }
+
+
+    #[test]
+    fn flush_between_schedules_completes_in_same_frame() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            FrameCountPlugin,
+            AsyncSystemPlugin {
+                execution: TaskExecution::Deterministic,
+                flush_between_schedules: true,
+                ..default()
+            }
+        ));
+        app.init_resource::<Frames>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system(PreUpdate, once::run(push_frame)).await;
+                schedules.add_system(Update, once::run(push_frame)).await;
+                schedules.add_system(PostUpdate, once::run(push_frame)).await;
+                schedules.add_system(PreUpdate, once::run(push_frame)).await;
+            });
+        });
+
+        app.update();
+        assert_eq!(app.world.resource::<Frames>().0, vec![0, 0, 0]);
+
+        app.update();
+        assert_eq!(app.world.resource::<Frames>().0, vec![0, 0, 0, 1]);
+    }
+
+
+    #[test]
+    fn custom_setup_schedule() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            FrameCountPlugin,
+            AsyncSystemPlugin {
+                execution: TaskExecution::Deterministic,
+                setup_schedule: Box::new(AsyncSetup),
+                ..default()
+            }
+        ));
+        app.init_schedule(AsyncSetup);
+        app.world.resource_mut::<MainScheduleOrder>().insert_after(PreUpdate, AsyncSetup);
+        app.init_resource::<Frames>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system(Update, once::run(push_frame)).await;
+                schedules.add_system(Update, once::run(push_frame)).await;
+            });
+        });
+
+        app.update();
+        app.update();
+
+        assert_eq!(app.world.resource::<Frames>().0, vec![0, 1]);
+        assert!(!app.world.resource::<Schedules>().get(&First).unwrap().graph().systems().any(|_| true));
+    }
+
+
+    #[derive(ScheduleLabel, Debug, Clone, Eq, PartialEq, Hash)]
+    struct AsyncSetup;
+
+
+    #[test]
+    fn strict_schedules_reject_unknown_schedule() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AsyncSystemPlugin {
+                execution: TaskExecution::Deterministic,
+                strict_schedules: true,
+                ..default()
+            }
+        ));
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system(FixedUpdate, once::run(|| {})).await;
+            });
+        });
+
+        app.update();
+
+        let task = app.world.query_filtered::<Entity, With<TaskHandle>>().single(&app.world);
+        let mut er = ManualEventReader::<AsyncTaskError>::default();
+        let errors: Vec<_> = er.iter(app.world.resource::<Events<AsyncTaskError>>()).cloned().collect();
+        assert_eq!(errors, vec![AsyncTaskError::UnknownSchedule {
+            task,
+            schedule: Box::new(FixedUpdate),
+        }]);
+        assert!(!app.world.resource::<Schedules>().contains(&FixedUpdate));
+    }
+
+
+    #[test]
+    fn fail_task_awaiting_unknown_schedule() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AsyncSystemPlugin {
+                execution: TaskExecution::Deterministic,
+                strict_schedules: true,
+                ..default()
+            }
+        ));
+        let task = app.world.spawn_async_restartable(|schedules| async move {
+            schedules.add_system(FixedUpdate, once::run(|| {})).await;
+        }).insert(Supervisor::new(RestartPolicy::OnPanic).with_max_restarts(0)).id();
+
+        app.update();
+        app.update();
+
+        let mut er = ManualEventReader::<SupervisedTaskGaveUp>::default();
+        let gave_up: Vec<_> = er.iter(app.world.resource::<Events<SupervisedTaskGaveUp>>()).cloned().collect();
+        assert_eq!(gave_up, vec![SupervisedTaskGaveUp {
+            task,
+            outcome: TaskOutcome::Panicked(format!("task {task:?} added a system to the unknown schedule FixedUpdate")),
+            restarts: 0,
+        }]);
+        assert!(app.world.get_entity(task).is_none());
+    }
+
+
+    #[test]
+    fn create_unknown_schedule_if_not_strict() {
+        let mut app = new_app();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async(|schedules| async move {
+                schedules.add_system(AsyncSetup, once::run(|| {})).await;
+            });
+        });
+
+        app.update();
+
+        assert!(app.world.resource::<Schedules>().contains(&AsyncSetup));
+        assert!(app.world.resource::<Events<AsyncTaskError>>().is_empty());
+    }
+
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn async_system_macro() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Frames>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            PushFrames::spawn(&mut commands);
+        });
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Frames>().0, vec![0, 2]);
+    }
+
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn register_async_system_as_task() {
+        use crate::async_task::SpawnAsyncTask;
+        use crate::ext::register_async_task::RegisterAsyncTask;
+
+        let mut app = new_deterministic_app();
+        app.init_resource::<Frames>();
+        app.register_async_task::<PushFrames>();
+        app.world.send_event(SpawnAsyncTask(PushFrames));
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Frames>().0, vec![0, 2]);
+    }
+
+
+    #[cfg(feature = "macros")]
+    #[crate::async_system]
+    async fn push_frames(schedules: crate::async_schedules::AsyncSchedules) {
+        let frame = ecs!(Update, |frame: Res<FrameCount>| frame.0);
+        ecs!(Update, move |mut frames: ResMut<Frames>| frames.0.push(frame));
+        ecs!(Update, push_frame);
+    }
+
+
+    #[derive(Resource, Default)]
+    struct Frames(Vec<u32>);
+
+
+    fn push_frame(frame: Res<FrameCount>, mut frames: ResMut<Frames>) {
+        frames.0.push(frame.0);
+    }
+}
+
+
 #[cfg(test)]
 pub(crate) mod test_util {
     use bevy::app::App;
@@ -68,8 +692,9 @@ pub(crate) mod test_util {
     use bevy::ecs::event::ManualEventReader;
     use bevy::prelude::{Event, Events, State, States};
     use bevy::time::TimePlugin;
+    use bevy::utils::default;
 
-    use crate::AsyncSystemPlugin;
+    use crate::{AsyncSystemPlugin, TaskExecution};
 
     #[derive(Event, Copy, Clone, Debug, Eq, PartialEq)]
     pub struct FirstEvent;
@@ -87,13 +712,27 @@ pub(crate) mod test_util {
     }
 
     pub fn new_app() -> App {
+        new_app_with(AsyncSystemPlugin::default())
+    }
+
+
+    /// Creates an app with [`TaskExecution::Deterministic`] for tests that count frames.
+    pub fn new_deterministic_app() -> App {
+        new_app_with(AsyncSystemPlugin {
+            execution: TaskExecution::Deterministic,
+            ..default()
+        })
+    }
+
+
+    fn new_app_with(plugin: AsyncSystemPlugin) -> App {
         let mut app = App::new();
         app.add_state::<TestState>();
         app.add_plugins((
             TaskPoolPlugin::default(),
             FrameCountPlugin,
             TimePlugin,
-            AsyncSystemPlugin
+            plugin
         ));
         app.add_event::<FirstEvent>();
         app.add_event::<SecondEvent>();
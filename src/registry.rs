@@ -0,0 +1,262 @@
+use bevy::core::Name;
+use bevy::hierarchy::DespawnRecursiveExt;
+use bevy::log::warn;
+use bevy::prelude::{Added, Commands, Component, Entity, Or, Query, ResMut, Resource, With};
+use bevy::utils::HashMap;
+
+use crate::async_schedules::TaskHandle;
+use crate::supervisor::PendingRestart;
+
+/// Determines what happens when a task is spawned by
+/// [`spawn_async_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_named)
+/// while another task with the same name is still running.
+///
+/// It is attached to the task entity, so it can be changed by inserting another value
+/// into the returned [`EntityCommands`](bevy::ecs::system::EntityCommands).
+#[derive(Component, Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DuplicateTaskPolicy {
+    /// The new task is despawned before it is polled, and the running task keeps its name.
+    #[default]
+    Reject,
+
+    /// The running task is despawned and the new task takes over the name.
+    Replace,
+}
+
+
+/// Maps the names of the tasks spawned by
+/// [`spawn_async_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_named) to their entities.
+///
+/// Named tasks are registered in [`AsyncSystemPlugin::setup_schedule`](crate::AsyncSystemPlugin::setup_schedule)
+/// before they are polled for the first time, and unregistered once they have been despawned.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     TaskPoolPlugin::default(),
+///     AsyncSystemPlugin::default()
+/// ));
+/// app.add_systems(Startup, |mut commands: Commands| {
+///     commands.spawn_async_named("intro_cutscene", |schedules| async move {
+///         schedules.add_system(Update, wait::until(|| false)).await;
+///     });
+/// });
+/// app.update();
+///
+/// assert!(app.world.resource::<AsyncTaskRegistry>().contains("intro_cutscene"));
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct AsyncTaskRegistry {
+    tasks: HashMap<String, Entity>,
+}
+
+
+impl AsyncTaskRegistry {
+    /// Returns the entity of the running task named `name`.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<Entity> {
+        self.tasks.get(name).copied()
+    }
+
+
+    /// Returns true if a task named `name` is running.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.tasks.contains_key(name)
+    }
+
+
+    /// Returns the names and entities of the running named tasks.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item=(&str, Entity)> {
+        self.tasks.iter().map(|(name, entity)| (name.as_str(), *entity))
+    }
+}
+
+
+pub(crate) fn register_named_tasks(
+    mut commands: Commands,
+    mut registry: ResMut<AsyncTaskRegistry>,
+    tasks: Query<(), Or<(With<TaskHandle>, With<PendingRestart>)>>,
+    new_tasks: Query<(Entity, &Name, &DuplicateTaskPolicy), (With<TaskHandle>, Added<DuplicateTaskPolicy>)>,
+) {
+    registry.tasks.retain(|_, entity| tasks.contains(*entity));
+
+    for (entity, name, policy) in new_tasks.iter() {
+        match (registry.get(name.as_str()), policy) {
+            (Some(running), _) if running == entity => {}
+            (Some(running), DuplicateTaskPolicy::Reject) => {
+                warn!("The task {name} is already running as {running:?}, so {entity:?} is despawned.");
+                commands.entity(entity).despawn_recursive();
+            }
+            (Some(running), DuplicateTaskPolicy::Replace) => {
+                commands.entity(running).despawn_recursive();
+                registry.tasks.insert(name.to_string(), entity);
+            }
+            (None, _) => {
+                registry.tasks.insert(name.to_string(), entity);
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Startup, Update};
+    use bevy::ecs::system::CommandQueue;
+    use bevy::prelude::{Commands, ResMut, Resource};
+
+    use crate::async_schedules::PausedTask;
+    use crate::ext::async_task_commands::AsyncTaskCommands;
+    use crate::ext::spawn_async_system::SpawnAsyncSystem;
+    use crate::registry::{AsyncTaskRegistry, DuplicateTaskPolicy};
+    use crate::runner::{once, repeat, wait};
+    use crate::test_util::new_deterministic_app;
+
+    #[derive(Resource, Default)]
+    struct Count(u32);
+
+
+    #[test]
+    fn reject_duplicate() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Count>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async_named("counter", |schedules| async move {
+                schedules.add_system(Update, repeat::forever(count_up)).await;
+            });
+        });
+        app.update();
+        let first = app.world.resource::<AsyncTaskRegistry>().get("counter").unwrap();
+
+        run_commands(&mut app, |commands| {
+            commands.spawn_async_named("counter", |schedules| async move {
+                schedules.add_system(Update, repeat::forever(count_up)).await;
+            });
+        });
+        app.update();
+        assert_eq!(app.world.resource::<AsyncTaskRegistry>().get("counter"), Some(first));
+        assert_eq!(app.world.resource::<AsyncTaskRegistry>().iter().count(), 1);
+        assert_eq!(app.world.resource::<Count>().0, 2);
+    }
+
+
+    #[test]
+    fn replace_duplicate() {
+        let mut app = new_deterministic_app();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async_named("cutscene", |schedules| async move {
+                schedules.add_system(Update, wait::until(|| false)).await;
+            });
+        });
+        app.update();
+        let first = app.world.resource::<AsyncTaskRegistry>().get("cutscene").unwrap();
+
+        run_commands(&mut app, |commands| {
+            commands
+                .spawn_async_named("cutscene", |schedules| async move {
+                    schedules.add_system(Update, wait::until(|| false)).await;
+                })
+                .insert(DuplicateTaskPolicy::Replace);
+        });
+        app.update();
+        let second = app.world.resource::<AsyncTaskRegistry>().get("cutscene").unwrap();
+        assert_ne!(first, second);
+        assert!(app.world.get_entity(first).is_none());
+    }
+
+
+    #[test]
+    fn cancel_by_name() {
+        let mut app = new_deterministic_app();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async_named("cutscene", |schedules| async move {
+                schedules.add_system(Update, wait::until(|| false)).await;
+            });
+        });
+        app.update();
+        let task = app.world.resource::<AsyncTaskRegistry>().get("cutscene").unwrap();
+
+        run_commands(&mut app, |commands| {
+            commands.cancel_async_task("cutscene");
+        });
+        assert!(app.world.get_entity(task).is_none());
+        app.update();
+        assert!(!app.world.resource::<AsyncTaskRegistry>().contains("cutscene"));
+    }
+
+
+    #[test]
+    fn pause_and_resume_by_name() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Count>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async_named("counter", |schedules| async move {
+                schedules.add_system(Update, repeat::times(3, count_up)).await;
+                schedules.add_system(Update, once::run(count_up)).await;
+            });
+        });
+        app.update();
+        assert_eq!(app.world.resource::<Count>().0, 1);
+
+        run_commands(&mut app, |commands| {
+            commands.pause_async_task("counter");
+        });
+        let task = app.world.resource::<AsyncTaskRegistry>().get("counter").unwrap();
+        assert!(app.world.get::<PausedTask>(task).is_some());
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Count>().0, 1);
+
+        run_commands(&mut app, |commands| {
+            commands.resume_async_task("counter");
+        });
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Count>().0, 3);
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Count>().0, 4);
+    }
+
+
+    #[test]
+    fn keep_name_across_restarts() {
+        let mut app = new_deterministic_app();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn_async_restartable_named("patrol", |schedules| async move {
+                schedules.add_system(Update, wait::until(|| false)).await;
+            });
+        });
+        app.update();
+        let task = app.world.resource::<AsyncTaskRegistry>().get("patrol").unwrap();
+
+        run_commands(&mut app, |commands| {
+            commands.restart_async_task("patrol");
+        });
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<AsyncTaskRegistry>().get("patrol"), Some(task));
+
+        run_commands(&mut app, |commands| {
+            commands.cancel_async_task("patrol");
+        });
+        assert!(app.world.get_entity(task).is_none());
+    }
+
+
+    fn run_commands(app: &mut App, f: impl FnOnce(&mut Commands)) {
+        let mut queue = CommandQueue::default();
+        f(&mut Commands::new(&mut queue, &app.world));
+        queue.apply(&mut app.world);
+    }
+
+
+    fn count_up(mut count: ResMut<Count>) {
+        count.0 += 1;
+    }
+}
@@ -0,0 +1,116 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use bevy::hierarchy::DespawnRecursiveExt;
+use bevy::log::warn;
+use bevy::prelude::{Bundle, Commands, Component, Entity, Event, EventReader, Query};
+
+use crate::async_schedules::{AsyncSchedules, BoxedTaskFuture};
+use crate::ext::spawn_async_system::{async_task_bundle, async_task_bundle_with_name};
+use crate::supervisor::PendingRestart;
+
+/// Restarts the task spawned by
+/// [`spawn_async_restartable`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_restartable).
+///
+/// The systems awaited by the task and the other children of the task entity are despawned,
+/// then the task is started again from the beginning on the same entity.
+/// The event is handled in [`AsyncSystemPlugin::setup_schedule`](crate::AsyncSystemPlugin::setup_schedule).
+#[derive(Event, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RestartAsyncTask(pub Entity);
+
+
+/// Holds the function that creates the future of a restartable task.
+#[derive(Component, Clone)]
+pub struct RestartableTask(Arc<dyn Fn(AsyncSchedules) -> BoxedTaskFuture + Send + Sync>);
+
+
+impl RestartableTask {
+    #[inline]
+    pub(crate) fn new<F>(f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> Self
+        where F: Future<Output=()> + Send + 'static
+    {
+        Self(Arc::new(move |schedules| Box::pin(f(schedules))))
+    }
+
+
+    /// Creates the components of a task entity that runs a new future.
+    #[inline]
+    pub(crate) fn task_bundle(&self) -> impl Bundle {
+        let f = Arc::clone(&self.0);
+        async_task_bundle(move |schedules| f(schedules))
+    }
+}
+
+
+/// `name` only names the span of the first run; restarted runs take it from the [`Name`](bevy::core::Name) of the task.
+pub(crate) fn restartable_async_task_bundle<F>(name: Option<&str>, f: impl Fn(AsyncSchedules) -> F + Send + Sync + 'static) -> impl Bundle
+    where F: Future<Output=()> + Send + 'static
+{
+    let task = RestartableTask::new(f);
+    let f = Arc::clone(&task.0);
+    (async_task_bundle_with_name(name, move |schedules| f(schedules)), task)
+}
+
+
+pub(crate) fn restart_async_tasks(
+    mut commands: Commands,
+    mut events: EventReader<RestartAsyncTask>,
+    tasks: Query<&RestartableTask>,
+) {
+    for RestartAsyncTask(entity) in events.iter().copied() {
+        let Ok(task) = tasks.get(entity) else {
+            warn!("{entity:?} is not a restartable task, so it cannot be restarted.");
+            continue;
+        };
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .remove::<PendingRestart>()
+            .insert(task.task_bundle());
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use bevy::app::Update;
+    use bevy::prelude::{Children, ResMut, Resource};
+
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::restart::RestartAsyncTask;
+    use crate::runner::{once, wait};
+    use crate::test_util::new_deterministic_app;
+
+    #[derive(Resource, Default)]
+    struct Count(u32);
+
+
+    #[test]
+    fn restart_from_beginning() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Count>();
+        let started = Arc::new(AtomicU32::new(0));
+        let counter = started.clone();
+        let task = app.spawn_async_restartable(move |schedules| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            async move {
+                schedules.add_system(Update, once::run(|mut count: ResMut<Count>| count.0 += 1)).await;
+                schedules.add_system(Update, wait::until(|| false)).await;
+            }
+        }).id();
+
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Count>().0, 1);
+        assert_eq!(app.world.get::<Children>(task).unwrap().len(), 2);
+
+        app.world.send_event(RestartAsyncTask(task));
+        app.update();
+        assert_eq!(started.load(Ordering::Relaxed), 2);
+        assert_eq!(app.world.resource::<Count>().0, 2);
+        assert_eq!(app.world.get::<Children>(task).unwrap().len(), 1);
+    }
+}
@@ -1,11 +1,14 @@
-use std::sync::{Arc, Mutex};
+use std::panic::Location;
+use std::sync::{Arc, Mutex, Weak};
 
-use bevy::ecs::schedule::ScheduleLabel;
+use bevy::app::{AppLabel, AppLabelId};
+use bevy::ecs::schedule::{BoxedScheduleLabel, ScheduleLabel};
 use bevy::ecs::system::EntityCommands;
-use bevy::hierarchy::BuildChildren;
-use bevy::prelude::{Component, Condition, Deref, DerefMut, Entity, IntoSystem, Query, Schedule, Schedules};
+use bevy::prelude::{Component, Condition, Deref, DerefMut, Entity, IntoSystem, Parent, Query, Schedule, Schedules, System, With};
 
-use crate::async_schedules::TaskSender;
+use crate::async_schedules::{PausedTask, TaskSender};
+use crate::diagnostics::AsyncRunnerInfo;
+use crate::error::AsyncTaskError;
 
 pub(crate) mod config;
 pub mod once;
@@ -15,6 +18,8 @@ pub mod delay;
 
 pub mod repeat;
 
+pub mod tween;
+
 
 pub mod preludes {
     pub use crate::runner::{
@@ -24,10 +29,17 @@ pub mod preludes {
         once,
         wait,
         delay,
-        repeat
+        repeat,
+        tween,
+        tween::Easing,
     };
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be awaited for an output of type `{Out}`",
+    label = "this runner does not output `{Out}`",
+    note = "`repeat`, `delay` and `wait::until` output `()`; use `once::run` or `wait::output` to get a value from a system"
+)]
 pub trait IntoAsyncScheduleCommand<Out = ()>: Sized {
     fn into_schedule_command(self, sender: TaskSender<Out>, schedule_label: impl ScheduleLabel + Clone) -> AsyncScheduleCommand;
 }
@@ -35,16 +47,147 @@ pub trait IntoAsyncScheduleCommand<Out = ()>: Sized {
 
 pub trait AsyncSchedule: Send + Sync {
     fn initialize(self: Box<Self>, entity_commands: &mut EntityCommands, schedules: &mut Schedules);
+
+
+    /// The name of the runner shown in [`AsyncRunnerInfo`], such as `once::run`.
+    fn kind(&self) -> &'static str {
+        "custom"
+    }
+
+
+    /// The type name of the system run by the runner, if any.
+    fn system_name(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 
+/// A runner waiting to be registered, with where and by whom it was added.
+///
+/// The runner can be accessed through [`Deref`](std::ops::Deref).
 #[derive(Component, Deref, DerefMut)]
-pub struct AsyncScheduleCommand(pub Box<dyn AsyncSchedule>);
+pub struct AsyncScheduleCommand {
+    #[deref]
+    runner: Box<dyn AsyncSchedule>,
+    target: Option<AppLabelId>,
+    schedule_label: Option<BoxedScheduleLabel>,
+    location: Option<&'static Location<'static>>,
+    awaiting: Weak<AwaitingOutput>,
+}
 
 impl AsyncScheduleCommand {
     #[inline]
     pub fn new(s: impl AsyncSchedule + 'static) -> Self {
-        Self(Box::new(s))
+        Self::from_boxed(Box::new(s))
+    }
+
+
+    /// Creates the command from a boxed runner.
+    #[inline]
+    pub fn from_boxed(runner: Box<dyn AsyncSchedule>) -> Self {
+        Self {
+            runner,
+            target: None,
+            schedule_label: None,
+            location: None,
+            awaiting: Weak::new(),
+        }
+    }
+
+
+    /// Returns the boxed runner.
+    #[inline]
+    pub fn into_runner(self) -> Box<dyn AsyncSchedule> {
+        self.runner
+    }
+
+
+    /// Routes the command to the world of the app labeled `app_label` instead of the world in which the task runs.
+    ///
+    /// See [`RouteAsyncSystems`](crate::ext::route_async_systems::RouteAsyncSystems).
+    #[inline]
+    pub fn with_target(mut self, app_label: impl AppLabel) -> Self {
+        self.target = Some(app_label.as_label());
+        self
+    }
+
+
+    /// Returns the label of the app this command is routed to,
+    /// or `None` if it targets the world in which the task runs.
+    #[inline]
+    pub const fn target(&self) -> Option<AppLabelId> {
+        self.target
+    }
+
+
+    /// Returns the label of the schedule the system is added to, if known.
+    #[inline]
+    pub fn schedule_label(&self) -> Option<&dyn ScheduleLabel> {
+        self.schedule_label.as_deref()
+    }
+
+
+    #[inline]
+    pub(crate) fn with_schedule_label(mut self, schedule_label: impl ScheduleLabel) -> Self {
+        self.schedule_label = Some(Box::new(schedule_label));
+        self
+    }
+
+
+    /// `awaiting` is alive while the output of the system is awaited.
+    #[inline]
+    pub(crate) fn with_caller(mut self, location: &'static Location<'static>, awaiting: Weak<AwaitingOutput>) -> Self {
+        self.location = Some(location);
+        self.awaiting = awaiting;
+        self
+    }
+
+
+    pub(crate) fn runner_info(&self, started_frame: Option<u32>) -> AsyncRunnerInfo {
+        AsyncRunnerInfo {
+            kind: self.runner.kind(),
+            system: self.runner.system_name(),
+            schedule: self.schedule_label.clone(),
+            started_frame,
+            location: self.location,
+            awaiting: self.awaiting.clone(),
+        }
+    }
+
+
+    #[inline]
+    pub(crate) fn initialize(self, entity_commands: &mut EntityCommands, schedules: &mut Schedules) {
+        self.runner.initialize(entity_commands, schedules);
+    }
+
+
+    /// Drops the command without registering it, so that the awaiting task panics with `error`.
+    pub(crate) fn reject(self, error: AsyncTaskError) {
+        if let Some(awaiting) = self.awaiting.upgrade() {
+            *awaiting.0.lock().unwrap() = Some(error);
+        }
+    }
+}
+
+
+/// Shared by a command and the future or task group awaiting its output, which holds the only strong reference.
+#[derive(Default)]
+pub(crate) struct AwaitingOutput(Mutex<Option<AsyncTaskError>>);
+
+
+impl AwaitingOutput {
+    /// Returns the error if the command was rejected.
+    #[inline]
+    pub(crate) fn rejection(&self) -> Option<AsyncTaskError> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+
+impl From<Box<dyn AsyncSchedule>> for AsyncScheduleCommand {
+    #[inline]
+    fn from(runner: Box<dyn AsyncSchedule>) -> Self {
+        Self::from_boxed(runner)
     }
 }
 
@@ -60,16 +203,16 @@ impl AsyncScheduleCommands {
     }
 
 
-    pub(crate) fn init_schedulers(
-        &self,
-        entity_commands: &mut EntityCommands,
-        schedules: &mut Schedules,
-    ) {
-        while let Some(system) = self.0.lock().unwrap().pop() {
-            let entity = entity_commands.commands().spawn_empty().id();
-            entity_commands.add_child(entity);
-            system.0.initialize(&mut entity_commands.commands().entity(entity), schedules);
-        }
+    /// Takes the commands pushed since the last call, in the order they were pushed.
+    #[inline]
+    pub(crate) fn take(&self) -> Vec<AsyncScheduleCommand> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+
+
+    #[inline]
+    pub(crate) fn downgrade(&self) -> Weak<Mutex<Vec<AsyncScheduleCommand>>> {
+        Arc::downgrade(&self.0)
     }
 }
 
@@ -85,14 +228,34 @@ fn task_running<Out>(entity: Entity) -> impl Condition<()>
     where
         Out: Send + 'static,
 {
-    IntoSystem::into_system(move |senders: Query<&TaskSender<Out>>| {
+    IntoSystem::into_system(move |senders: Query<&TaskSender<Out>>, parents: Query<&Parent>, paused: Query<(), With<PausedTask>>| {
         senders
             .get(entity)
             .is_ok_and(|sender| !sender.is_closed())
+            && !parents.get(entity).is_ok_and(|task| paused.contains(task.get()))
     })
 }
 
 
+/// Makes `system` enter the [`RunnerSpan`](crate::trace::RunnerSpan) of the runner `entity` while it runs.
+#[cfg(feature = "tracing")]
+#[inline]
+fn traced<Out, Marker>(entity: Entity, system: impl IntoSystem<(), Out, Marker>) -> impl System<In=(), Out=Out>
+    where Out: 'static
+{
+    crate::trace::Traced::new(entity, IntoSystem::into_system(system))
+}
+
+
+#[cfg(not(feature = "tracing"))]
+#[inline]
+fn traced<Out, Marker>(_: Entity, system: impl IntoSystem<(), Out, Marker>) -> impl System<In=(), Out=Out>
+    where Out: 'static
+{
+    IntoSystem::into_system(system)
+}
+
+
 fn schedule_initialize<'a, Label: ScheduleLabel + Clone>(schedules: &'a mut Schedules, schedule_label: &Label) -> &'a mut Schedule {
     if !schedules.contains(schedule_label) {
         schedules.insert(schedule_label.clone(), Schedule::default());
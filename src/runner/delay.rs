@@ -32,6 +32,8 @@ pub const fn frames(delay_frames: usize) -> impl IntoAsyncScheduleCommand {
 
 /// Delays the task using a [`Timer`](bevy::prelude::Timer).
 ///
+/// The timer is ticked by [`AsyncClock`](crate::clock::AsyncClock), which follows [`Time`](bevy::time::Time) by default.
+///
 ///
 /// ## Examples
 ///
@@ -4,7 +4,7 @@ use bevy::prelude::{IntoSystemConfigs, Local, Query, Schedules};
 
 use crate::async_schedules::TaskSender;
 use crate::prelude::{AsyncScheduleCommand, IntoAsyncScheduleCommand};
-use crate::runner::{AsyncSchedule, schedule_initialize, task_running};
+use crate::runner::{AsyncSchedule, schedule_initialize, task_running, traced};
 
 pub(crate) struct DelayFrame(pub usize);
 
@@ -33,7 +33,7 @@ impl<Label: ScheduleLabel + Clone> AsyncSchedule for Scheduler<Label> {
         entity_commands.insert(self.sender);
         let entity = entity_commands.id();
         let delay_frames = self.delay_frames;
-        schedule.add_systems((move |mut frame_count: Local<usize>, mut senders: Query<&mut TaskSender<()>>| {
+        schedule.add_systems(traced(entity, move |mut frame_count: Local<usize>, mut senders: Query<&mut TaskSender<()>>| {
             *frame_count += 1;
             if delay_frames <= *frame_count {
                 let Ok(mut sender) = senders.get_mut(entity) else { return; };
@@ -42,6 +42,10 @@ impl<Label: ScheduleLabel + Clone> AsyncSchedule for Scheduler<Label> {
             }
         }).run_if(task_running::<()>(entity)));
     }
+
+    fn kind(&self) -> &'static str {
+        "delay::frames"
+    }
 }
 
 
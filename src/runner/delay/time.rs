@@ -3,11 +3,12 @@ use std::time::Duration;
 use bevy::ecs::schedule::ScheduleLabel;
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::{Component, IntoSystemConfigs, Query, Res, Schedules, TimerMode};
-use bevy::time::{Time, Timer};
+use bevy::time::Timer;
 
 use crate::async_schedules::TaskSender;
+use crate::clock::AsyncClock;
 use crate::prelude::AsyncScheduleCommand;
-use crate::runner::{AsyncSchedule, IntoAsyncScheduleCommand, schedule_initialize, task_running};
+use crate::runner::{AsyncSchedule, IntoAsyncScheduleCommand, schedule_initialize, task_running, traced};
 
 pub(crate) struct DelayTime(pub Duration);
 
@@ -43,14 +44,18 @@ impl<Label: ScheduleLabel + Clone> AsyncSchedule for Executor<Label> {
         ));
         let entity = entity_commands.id();
 
-        schedule.add_systems((move |time: Res<Time>, mut query: Query<(&mut TaskSender<()>, &mut LocalTimer)>| {
+        schedule.add_systems(traced(entity, move |clock: Res<AsyncClock>, mut query: Query<(&mut TaskSender<()>, &mut LocalTimer)>| {
             let Ok((mut sender, mut timer)) = query.get_mut(entity) else { return; };
-            if timer.0.tick(time.delta()).just_finished() {
+            if timer.0.tick(clock.delta()).just_finished() {
                 let _ = sender.try_send(());
                 sender.close_channel();
             }
         }).run_if(task_running::<()>(entity)));
     }
+
+    fn kind(&self) -> &'static str {
+        "delay::timer"
+    }
 }
 
 
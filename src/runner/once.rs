@@ -5,9 +5,15 @@ use bevy::prelude::{Commands, Event, EventWriter, FromWorld, In, IntoSystem, Int
 
 use crate::async_schedules::TaskSender;
 use crate::prelude::{AsyncSchedule, AsyncScheduleCommand, IntoAsyncScheduleCommand};
-use crate::runner::{schedule_initialize, task_running};
+use crate::runner::{schedule_initialize, task_running, traced};
 use crate::runner::config::AsyncSystemConfig;
 
+#[cfg(feature = "audio")]
+mod audio;
+
+#[cfg(feature = "audio")]
+pub use audio::*;
+
 /// Run the system only once.
 ///
 /// The system can use `Output`.
@@ -48,7 +54,22 @@ pub fn run<Out, Marker, Sys>(system: Sys) -> impl IntoAsyncScheduleCommand<Out>
         Marker: Send + Sync + 'static,
         Sys: IntoSystem<(), Out, Marker> + Send + Sync + 'static
 {
-    OnceOnMain(AsyncSystemConfig::<Out, Marker, Sys>::new(system))
+    run_as("once::run", system)
+}
+
+
+/// Same as [`run`], but the runner is described as `kind` in [`AsyncRunnerInfo`](crate::diagnostics::AsyncRunnerInfo).
+#[inline(always)]
+pub(crate) fn run_as<Out, Marker, Sys>(kind: &'static str, system: Sys) -> impl IntoAsyncScheduleCommand<Out>
+    where
+        Out: Send + Sync + 'static,
+        Marker: Send + Sync + 'static,
+        Sys: IntoSystem<(), Out, Marker> + Send + Sync + 'static
+{
+    OnceOnMain {
+        config: AsyncSystemConfig::<Out, Marker, Sys>::new(system),
+        kind,
+    }
 }
 
 
@@ -74,7 +95,7 @@ pub fn run<Out, Marker, Sys>(system: Sys) -> impl IntoAsyncScheduleCommand<Out>
 ///
 #[inline]
 pub fn set_state<S: States + Copy>(to: S) -> impl IntoAsyncScheduleCommand {
-    run(move |mut state: ResMut<NextState<S>>| {
+    run_as("once::set_state", move |mut state: ResMut<NextState<S>>| {
         state.set(to);
     })
 }
@@ -99,7 +120,7 @@ pub fn set_state<S: States + Copy>(to: S) -> impl IntoAsyncScheduleCommand {
 /// ```
 #[inline]
 pub fn send<E: Event + Clone>(event: E) -> impl IntoAsyncScheduleCommand {
-    run(move |mut ew: EventWriter<E>| {
+    run_as("once::send", move |mut ew: EventWriter<E>| {
         ew.send(event.clone());
     })
 }
@@ -108,7 +129,9 @@ pub fn send<E: Event + Clone>(event: E) -> impl IntoAsyncScheduleCommand {
 /// Send [`AppExit`].
 #[inline(always)]
 pub fn app_exit() -> impl IntoAsyncScheduleCommand {
-    send(AppExit)
+    run_as("once::app_exit", |mut ew: EventWriter<AppExit>| {
+        ew.send(AppExit);
+    })
 }
 
 
@@ -132,7 +155,7 @@ pub fn app_exit() -> impl IntoAsyncScheduleCommand {
 /// ```
 #[inline]
 pub fn insert_resource<R: Resource + Clone>(resource: R) -> impl IntoAsyncScheduleCommand {
-    run(move |mut commands: Commands| {
+    run_as("once::insert_resource", move |mut commands: Commands| {
         commands.insert_resource(resource.clone());
     })
 }
@@ -155,7 +178,7 @@ pub fn insert_resource<R: Resource + Clone>(resource: R) -> impl IntoAsyncSchedu
 /// ```
 #[inline]
 pub fn init_resource<R: Resource + Default>() -> impl IntoAsyncScheduleCommand {
-    run(|mut commands: Commands| {
+    run_as("once::init_resource", |mut commands: Commands| {
         commands.init_resource::<R>();
     })
 }
@@ -179,13 +202,16 @@ pub fn init_resource<R: Resource + Default>() -> impl IntoAsyncScheduleCommand {
 /// ```
 #[inline]
 pub fn init_non_send_resource<R: FromWorld + 'static>() -> impl IntoAsyncScheduleCommand {
-    run(move |world: &mut World| {
+    run_as("once::init_non_send_resource", move |world: &mut World| {
         world.init_non_send_resource::<R>();
     })
 }
 
 
-struct OnceOnMain<Out, Marker, Sys>(AsyncSystemConfig<Out, Marker, Sys>);
+struct OnceOnMain<Out, Marker, Sys> {
+    config: AsyncSystemConfig<Out, Marker, Sys>,
+    kind: &'static str,
+}
 
 
 impl<Out, Marker, Sys> IntoAsyncScheduleCommand<Out> for OnceOnMain<Out, Marker, Sys>
@@ -196,9 +222,10 @@ impl<Out, Marker, Sys> IntoAsyncScheduleCommand<Out> for OnceOnMain<Out, Marker,
 {
     fn into_schedule_command(self, sender: TaskSender<Out>, schedule_label: impl ScheduleLabel + Clone) -> AsyncScheduleCommand {
         AsyncScheduleCommand::new(OnceRunner {
-            config: self.0,
+            config: self.config,
             sender,
             schedule_label,
+            kind: self.kind,
         })
     }
 }
@@ -208,6 +235,7 @@ struct OnceRunner<Out, Marker, Sys, Label> {
     config: AsyncSystemConfig<Out, Marker, Sys>,
     sender: TaskSender<Out>,
     schedule_label: Label,
+    kind: &'static str,
 }
 
 
@@ -222,7 +250,7 @@ impl<Out, Marker, Sys, Label> AsyncSchedule for OnceRunner<Out, Marker, Sys, Lab
         let schedule = schedule_initialize(schedules, &self.schedule_label);
         entity_commands.insert(self.sender);
         let entity = entity_commands.id();
-        schedule.add_systems(self
+        schedule.add_systems(traced(entity, self
             .config
             .system
             .pipe(move |In(input): In<Out>, mut senders: Query<&mut TaskSender<Out>>| {
@@ -230,9 +258,18 @@ impl<Out, Marker, Sys, Label> AsyncSchedule for OnceRunner<Out, Marker, Sys, Lab
                     let _ = sender.try_send(input);
                     sender.close_channel();
                 }
-            })
+            }))
             .run_if(task_running::<Out>(entity)));
     }
+
+    fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+
+    fn system_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<Sys>())
+    }
 }
 
 
@@ -0,0 +1,41 @@
+use bevy::asset::Handle;
+use bevy::audio::{AudioBundle, AudioSource, PlaybackSettings};
+use bevy::prelude::{Commands, Entity};
+
+use crate::runner::IntoAsyncScheduleCommand;
+use crate::runner::once::run_as;
+
+/// Spawns an entity that plays `source` once and outputs the entity.
+///
+/// Await [`wait::audio_finished`](crate::runner::wait::audio_finished) with the entity to continue when the playback ends.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let voice = asset_server.load("audio/voice.ogg");
+///     commands.spawn_async(|schedules| async move {
+///         let audio = schedules.add_system(Update, once::play_audio(voice)).await;
+///         schedules.add_system(Update, wait::audio_finished(audio)).await;
+///     });
+/// }
+/// ```
+#[inline]
+pub fn play_audio(source: Handle<AudioSource>) -> impl IntoAsyncScheduleCommand<Entity> {
+    play_audio_with_settings(source, PlaybackSettings::ONCE)
+}
+
+
+/// Spawns an entity that plays `source` with `settings` and outputs the entity.
+#[inline]
+pub fn play_audio_with_settings(source: Handle<AudioSource>, settings: PlaybackSettings) -> impl IntoAsyncScheduleCommand<Entity> {
+    run_as("once::play_audio", move |mut commands: Commands| {
+        commands
+            .spawn(AudioBundle {
+                source: source.clone(),
+                settings,
+            })
+            .id()
+    })
+}
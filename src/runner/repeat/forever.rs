@@ -4,7 +4,7 @@ use bevy::prelude::{IntoSystem, IntoSystemConfigs, Schedules};
 
 use crate::async_schedules::TaskSender;
 use crate::prelude::{AsyncScheduleCommand, IntoAsyncScheduleCommand};
-use crate::runner::{AsyncSchedule, schedule_initialize, task_running};
+use crate::runner::{AsyncSchedule, schedule_initialize, task_running, traced};
 use crate::runner::config::AsyncSystemConfig;
 
 pub(crate) struct Forever<Marker, Sys>(pub AsyncSystemConfig<(), Marker, Sys>);
@@ -42,7 +42,16 @@ impl<Marker, Sys, Label> AsyncSchedule for Scheduler<Marker, Sys, Label>
         let schedule = schedule_initialize(schedules, &self.schedule_label);
         entity_commands.insert(self.sender);
         let entity = entity_commands.id();
-        schedule.add_systems(self.config.system.run_if(task_running::<()>(entity)));
+        schedule.add_systems(traced(entity, self.config.system).run_if(task_running::<()>(entity)));
+    }
+
+    fn kind(&self) -> &'static str {
+        "repeat::forever"
+    }
+
+
+    fn system_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<Sys>())
     }
 }
 
@@ -54,7 +63,7 @@ mod tests {
 
     use crate::ext::spawn_async_system::SpawnAsyncSystem;
     use crate::runner::{delay, repeat};
-    use crate::test_util::new_app;
+    use crate::test_util::{new_app, new_deterministic_app};
 
     #[test]
     fn repeat_forever() {
@@ -75,7 +84,7 @@ mod tests {
 
     #[test]
     fn when_drop_handle_system_also_stop() {
-        let mut app = new_app();
+        let mut app = new_deterministic_app();
         app.insert_resource(Count(0));
         app.add_systems(Startup, |mut commands: Commands| {
             commands.spawn_async(|schedules| async move {
@@ -4,7 +4,7 @@ use bevy::prelude::{IntoSystem, IntoSystemConfigs, Local, Query, Schedules};
 
 use crate::async_schedules::TaskSender;
 use crate::prelude::{AsyncSchedule, AsyncScheduleCommand, IntoAsyncScheduleCommand};
-use crate::runner::{schedule_initialize, task_running};
+use crate::runner::{schedule_initialize, task_running, traced};
 use crate::runner::config::AsyncSystemConfig;
 
 pub(crate) struct Times<Marker, Sys> {
@@ -63,7 +63,7 @@ impl<Marker, Sys, Label: ScheduleLabel + Clone> AsyncSchedule for Scheduler<Mark
         let entity = entity_commands.id();
         let request_repeat_num = self.repeat_num;
         schedule.add_systems((
-            self.config.system,
+            traced(entity, self.config.system),
             move |mut repeat_num: Local<usize>, mut senders: Query<&mut TaskSender<()>>| {
                 *repeat_num += 1;
                 if request_repeat_num <= *repeat_num {
@@ -76,6 +76,15 @@ impl<Marker, Sys, Label: ScheduleLabel + Clone> AsyncSchedule for Scheduler<Mark
             .chain()
             .run_if(task_running::<()>(entity)));
     }
+
+    fn kind(&self) -> &'static str {
+        "repeat::times"
+    }
+
+
+    fn system_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<Sys>())
+    }
 }
 
 
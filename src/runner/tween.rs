@@ -0,0 +1,250 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use bevy::prelude::{Component, Entity, Local, Query, Res, Transform};
+
+use crate::clock::AsyncClock;
+use crate::runner::IntoAsyncScheduleCommand;
+use crate::runner::wait::until_as;
+
+/// The rate of change of a tween over time.
+#[derive(Debug, Default, Copy, Clone)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineInOut,
+
+    /// Maps the progress from `0.0` to `1.0` to the interpolation factor.
+    Custom(fn(f32) -> f32),
+}
+
+
+impl Easing {
+    /// Returns the interpolation factor at `t`, the progress from `0.0` to `1.0`.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Self::Linear => t,
+            Self::QuadIn => t * t,
+            Self::QuadOut => 1. - (1. - t) * (1. - t),
+            Self::QuadInOut => if t < 0.5 {
+                2. * t * t
+            } else {
+                1. - (-2. * t + 2.).powi(2) / 2.
+            },
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => 1. - (1. - t).powi(3),
+            Self::CubicInOut => if t < 0.5 {
+                4. * t * t * t
+            } else {
+                1. - (-2. * t + 2.).powi(3) / 2.
+            },
+            Self::SineInOut => -((PI * t).cos() - 1.) / 2.,
+            Self::Custom(f) => f(t)
+        }
+    }
+}
+
+
+/// Interpolates between two values of a component.
+pub trait Lerp {
+    /// Returns the value between `self` at `t = 0.0` and `to` at `t = 1.0`.
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+
+impl Lerp for Transform {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            translation: self.translation.lerp(to.translation, t),
+            rotation: self.rotation.slerp(to.rotation, t),
+            scale: self.scale.lerp(to.scale, t),
+        }
+    }
+}
+
+
+/// Moves the [`Transform`] of `entity` from its current value to `target` over `duration`.
+///
+/// The tween starts from the value the transform has when the system first runs,
+/// and resolves after the transform is set to `target`.
+/// The time is measured with [`AsyncClock`].
+/// If the entity does not have [`Transform`], it resolves immediately.
+///
+/// Tweens are independent systems, so several of them can be awaited together to animate in parallel.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+/// use bevy_async_system::runner::tween::Lerp;
+///
+/// #[derive(Component)]
+/// struct Opacity(f32);
+///
+/// impl Lerp for Opacity {
+///     fn lerp(&self, to: &Self, t: f32) -> Self {
+///         Self(self.0 + (to.0 - self.0) * t)
+///     }
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     let shape = commands.spawn((SpatialBundle::default(), Opacity(0.))).id();
+///     commands.spawn_async(move |schedules| async move {
+///         let target = Transform::from_xyz(0., 300., 0.);
+///         schedules.add_system(Update, tween::transform_to(shape, target, Duration::from_secs(1), Easing::QuadOut)).await;
+///
+///         // Each tween animates a different component, so they do not overwrite each other.
+///         futures::join!(
+///             schedules.add_system(Update, tween::transform_to(shape, target.with_scale(Vec3::splat(2.)), Duration::from_secs(2), Easing::Linear)),
+///             schedules.add_system(Update, tween::lerp(shape, Opacity(0.), Opacity(1.), Duration::from_secs(1), Easing::SineInOut)),
+///         );
+///     });
+/// }
+/// ```
+#[inline]
+pub fn transform_to(entity: Entity, target: Transform, duration: Duration, easing: Easing) -> impl IntoAsyncScheduleCommand {
+    until_as("tween::transform_to", move |mut from: Local<Option<Transform>>, mut elapsed: Local<Option<Duration>>, clock: Res<AsyncClock>, mut transforms: Query<&mut Transform>| {
+        let Ok(mut transform) = transforms.get_mut(entity) else { return true; };
+        let from = *from.get_or_insert(*transform);
+        step(&from, &target, &mut transform, &mut elapsed, &clock, duration, easing)
+    })
+}
+
+
+/// Animates the component `C` of `entity` from `from` to `to` over `duration`.
+///
+/// Resolves after the component is set to `to`.
+/// The time is measured with [`AsyncClock`].
+/// If the entity does not have `C`, it resolves immediately.
+#[inline]
+pub fn lerp<C>(entity: Entity, from: C, to: C, duration: Duration, easing: Easing) -> impl IntoAsyncScheduleCommand
+    where C: Component + Lerp + Send + Sync + 'static
+{
+    until_as("tween::lerp", move |mut elapsed: Local<Option<Duration>>, clock: Res<AsyncClock>, mut components: Query<&mut C>| {
+        let Ok(mut component) = components.get_mut(entity) else { return true; };
+        step(&from, &to, &mut component, &mut elapsed, &clock, duration, easing)
+    })
+}
+
+
+/// Sets the value at the elapsed time and returns true once it has reached `to`.
+///
+/// The first call sets `from` without advancing the time, since the delta of that frame elapsed before the tween started.
+fn step<C: Lerp>(
+    from: &C,
+    to: &C,
+    current: &mut C,
+    elapsed: &mut Option<Duration>,
+    clock: &AsyncClock,
+    duration: Duration,
+    easing: Easing,
+) -> bool {
+    let elapsed = match elapsed {
+        Some(elapsed) => {
+            *elapsed += clock.delta();
+            *elapsed
+        }
+        None => *elapsed.insert(Duration::ZERO)
+    };
+    let t = if duration.is_zero() {
+        1.
+    } else {
+        elapsed.as_secs_f32() / duration.as_secs_f32()
+    };
+    *current = from.lerp(to, easing.apply(t));
+    1. <= t
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::app::Update;
+    use bevy::math::Vec3;
+    use bevy::prelude::{Component, Transform};
+
+    use crate::clock::{AsyncClock, ManualAsyncClock};
+    use crate::diagnostics::AsyncTaskDiagnostics;
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::tween::{self, Easing, Lerp};
+    use crate::test_util::new_deterministic_app;
+
+    #[derive(Component, Debug, Copy, Clone, PartialEq)]
+    struct Opacity(f32);
+
+
+    impl Lerp for Opacity {
+        fn lerp(&self, to: &Self, t: f32) -> Self {
+            Self(self.0 + (to.0 - self.0) * t)
+        }
+    }
+
+
+    #[test]
+    fn easing_bounds() {
+        for (easing, half) in [
+            (Easing::Linear, 0.5),
+            (Easing::QuadIn, 0.25),
+            (Easing::QuadOut, 0.75),
+            (Easing::QuadInOut, 0.5),
+            (Easing::CubicIn, 0.125),
+            (Easing::CubicOut, 0.875),
+            (Easing::CubicInOut, 0.5),
+            (Easing::SineInOut, 0.5),
+        ] {
+            assert!(easing.apply(0.).abs() < f32::EPSILON);
+            assert!((easing.apply(1.) - 1.).abs() < f32::EPSILON);
+            assert!((easing.apply(0.5) - half).abs() < 1e-6, "{easing:?} at 0.5 was {}", easing.apply(0.5));
+        }
+    }
+
+
+    #[test]
+    fn transform_and_lerp_in_parallel() {
+        let clock = ManualAsyncClock::default();
+        let mut app = new_deterministic_app();
+        app.insert_resource(AsyncClock::manual(clock.clone()));
+        app.init_resource::<AsyncTaskDiagnostics>();
+        let shape = app.world.spawn((Transform::default(), Opacity(0.))).id();
+        let task = app.spawn_async(move |schedules| async move {
+            futures::join!(
+                schedules.add_system(Update, tween::transform_to(shape, Transform::from_xyz(10., 0., 0.), Duration::from_secs(1), Easing::Linear)),
+                schedules.add_system(Update, tween::lerp(shape, Opacity(1.), Opacity(0.), Duration::from_secs(2), Easing::Linear)),
+            );
+        }).id();
+
+        app.update();
+        app.update();
+        assert_eq!(app.world.get::<Transform>(shape).unwrap().translation, Vec3::ZERO);
+        assert_eq!(app.world.get::<Opacity>(shape), Some(&Opacity(1.)));
+        let kinds = app.world.resource::<AsyncTaskDiagnostics>().tasks()[0]
+            .awaiting
+            .iter()
+            .map(|runner| runner.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["tween::transform_to", "tween::lerp"]);
+
+        clock.advance(Duration::from_millis(500));
+        app.update();
+        assert_eq!(app.world.get::<Transform>(shape).unwrap().translation, Vec3::new(5., 0., 0.));
+        assert_eq!(app.world.get::<Opacity>(shape), Some(&Opacity(0.75)));
+
+        clock.advance(Duration::from_millis(500));
+        app.update();
+        assert_eq!(app.world.get::<Transform>(shape).unwrap().translation, Vec3::new(10., 0., 0.));
+
+        clock.advance(Duration::from_secs(1));
+        app.update();
+        app.update();
+        assert_eq!(app.world.get::<Opacity>(shape), Some(&Opacity(0.)));
+        assert!(app.world.get_entity(task).is_none());
+    }
+}
@@ -1,7 +1,22 @@
 mod until;
 mod output;
+#[cfg(feature = "input")]
+mod input;
+#[cfg(feature = "audio")]
+mod audio;
+#[cfg(feature = "ui")]
+mod ui;
 
 
 pub use output::*;
 
+#[cfg(feature = "input")]
+pub use input::*;
+
+#[cfg(feature = "audio")]
+pub use audio::*;
+
+#[cfg(feature = "ui")]
+pub use ui::*;
+
 pub use until::*;
\ No newline at end of file
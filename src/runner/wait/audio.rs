@@ -0,0 +1,176 @@
+use bevy::asset::Handle;
+use bevy::audio::{AudioSink, AudioSinkPlayback, AudioSource};
+use bevy::prelude::{Component, Entity, Query};
+
+use crate::runner::IntoAsyncScheduleCommand;
+use crate::runner::wait::until_as;
+
+/// Waits until the audio played by `entity` finishes.
+///
+/// It resolves when the [`AudioSink`] of the entity is empty, or when the entity has been despawned or its audio components removed,
+/// as happens with [`PlaybackMode::Despawn`](bevy::audio::PlaybackMode::Despawn) and [`PlaybackMode::Remove`](bevy::audio::PlaybackMode::Remove).
+/// While the audio source is still loading, the entity has no sink yet and it keeps waiting.
+///
+/// See [`once::play_audio`](crate::runner::once::play_audio) for an example.
+#[inline]
+pub fn audio_finished(entity: Entity) -> impl IntoAsyncScheduleCommand {
+    wait_sink::<AudioSink>("wait::audio_finished", entity)
+}
+
+
+/// Like [`audio_finished`], but waits on the sink component `S`,
+/// such as [`SpatialAudioSink`](bevy::audio::SpatialAudioSink).
+#[inline]
+pub fn sink_finished<S>(entity: Entity) -> impl IntoAsyncScheduleCommand
+    where S: Component + AudioSinkPlayback
+{
+    wait_sink::<S>("wait::sink_finished", entity)
+}
+
+
+#[inline]
+fn wait_sink<S>(kind: &'static str, entity: Entity) -> impl IntoAsyncScheduleCommand
+    where S: Component + AudioSinkPlayback
+{
+    until_as(kind, move |audio: Query<(Option<&S>, Option<&Handle<AudioSource>>)>| {
+        match audio.get(entity) {
+            Ok((Some(sink), _)) => sink.empty(),
+            Ok((None, source)) => source.is_none(),
+            Err(_) => true
+        }
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use bevy::app::Update;
+    use bevy::asset::Handle;
+    use bevy::audio::{AudioSinkPlayback, AudioSource, PlaybackMode, PlaybackSettings};
+    use bevy::prelude::{Component, Entity, ResMut, Resource};
+
+    use crate::diagnostics::AsyncTaskDiagnostics;
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::{once, wait};
+    use crate::test_util::new_deterministic_app;
+
+    /// Stands in for `AudioSink`, which needs an audio device.
+    #[derive(Component, Default)]
+    struct TestSink(AtomicBool);
+
+
+    impl AudioSinkPlayback for TestSink {
+        fn volume(&self) -> f32 {
+            1.
+        }
+
+        fn set_volume(&self, _: f32) {}
+
+        fn speed(&self) -> f32 {
+            1.
+        }
+
+        fn set_speed(&self, _: f32) {}
+
+        fn play(&self) {}
+
+        fn pause(&self) {}
+
+        fn is_paused(&self) -> bool {
+            false
+        }
+
+        fn stop(&self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+
+        fn empty(&self) -> bool {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+
+
+    #[derive(Resource, Default)]
+    struct Played(Option<Entity>, bool);
+
+
+    #[test]
+    fn play_and_wait_until_finished() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Played>();
+        app.spawn_async(|schedules| async move {
+            let audio = schedules.add_system(Update, once::play_audio(Handle::default())).await;
+            schedules.add_system(Update, once::run(move |mut played: ResMut<Played>| played.0 = Some(audio))).await;
+            schedules.add_system(Update, wait::sink_finished::<TestSink>(audio)).await;
+            schedules.add_system(Update, once::run(|mut played: ResMut<Played>| played.1 = true)).await;
+        });
+
+        for _ in 0..3 {
+            app.update();
+        }
+        let audio = app.world.resource::<Played>().0.unwrap();
+        assert!(app.world.get::<Handle<AudioSource>>(audio).is_some());
+        assert!(matches!(app.world.get::<PlaybackSettings>(audio).unwrap().mode, PlaybackMode::Once));
+
+        // loading
+        app.update();
+        app.update();
+        assert!(!app.world.resource::<Played>().1);
+
+        app.world.entity_mut(audio).insert(TestSink::default());
+        app.update();
+        app.update();
+        assert!(!app.world.resource::<Played>().1);
+
+        app.world.get::<TestSink>(audio).unwrap().stop();
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Played>().1);
+    }
+
+
+    #[test]
+    fn describe_audio_waits_by_constructor() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<AsyncTaskDiagnostics>();
+        let audio = app.world.spawn(Handle::<AudioSource>::default()).id();
+        app.spawn_async(move |schedules| async move {
+            futures::join!(
+                schedules.add_system(Update, wait::audio_finished(audio)),
+                schedules.add_system(Update, wait::sink_finished::<TestSink>(audio)),
+            );
+        });
+
+        app.update();
+        app.update();
+        let kinds = app.world.resource::<AsyncTaskDiagnostics>().tasks()[0]
+            .awaiting
+            .iter()
+            .map(|runner| runner.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["wait::audio_finished", "wait::sink_finished"]);
+    }
+
+
+    #[test]
+    fn finish_when_despawned() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Played>();
+        let audio = app.world.spawn((Handle::<AudioSource>::default(), TestSink::default())).id();
+        app.spawn_async(move |schedules| async move {
+            schedules.add_system(Update, wait::sink_finished::<TestSink>(audio)).await;
+            schedules.add_system(Update, once::run(|mut played: ResMut<Played>| played.1 = true)).await;
+        });
+
+        app.update();
+        app.update();
+        assert!(!app.world.resource::<Played>().1);
+
+        app.world.despawn(audio);
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Played>().1);
+    }
+}
@@ -0,0 +1,385 @@
+use std::hash::Hash;
+use std::time::Duration;
+
+use bevy::input::Input;
+use bevy::prelude::{Local, Res};
+
+use crate::clock::AsyncClock;
+use crate::runner::IntoAsyncScheduleCommand;
+use crate::runner::wait::output_as;
+
+/// Waits until `input` is just pressed and outputs it.
+///
+/// Works with any [`Input`] resource, such as `Input<KeyCode>` or `Input<MouseButton>`.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn_async(|schedules| async move {
+///         schedules.add_system(Update, wait::key_pressed(KeyCode::Space)).await;
+///         schedules.add_system(Update, wait::key_pressed(MouseButton::Left)).await;
+///     });
+/// }
+/// ```
+#[inline]
+pub fn key_pressed<T>(input: T) -> impl IntoAsyncScheduleCommand<T>
+    where T: Copy + Eq + Hash + Send + Sync + 'static
+{
+    output_as("wait::key_pressed", move |inputs: Res<Input<T>>| {
+        inputs.just_pressed(input).then_some(input)
+    })
+}
+
+
+/// Waits until any input of `T` is just pressed and outputs it.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn_async(|schedules| async move {
+///         let key: KeyCode = schedules.add_system(Update, wait::any_key()).await;
+///         println!("{key:?} was pressed");
+///     });
+/// }
+/// ```
+#[inline]
+pub fn any_key<T>() -> impl IntoAsyncScheduleCommand<T>
+    where T: Copy + Eq + Hash + Send + Sync + 'static
+{
+    output_as("wait::any_key", |inputs: Res<Input<T>>| {
+        inputs.get_just_pressed().next().copied()
+    })
+}
+
+
+/// Waits until `input` has been held for `duration` and outputs it.
+///
+/// The time is measured with [`AsyncClock`] and starts over whenever the input is released.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn_async(|schedules| async move {
+///         schedules.add_system(Update, wait::button_held(KeyCode::E, Duration::from_secs(1))).await;
+///         println!("charged");
+///     });
+/// }
+/// ```
+#[inline]
+pub fn button_held<T>(input: T, duration: Duration) -> impl IntoAsyncScheduleCommand<T>
+    where T: Copy + Eq + Hash + Send + Sync + 'static
+{
+    output_as("wait::button_held", move |mut held: Local<Option<Duration>>, inputs: Res<Input<T>>, clock: Res<AsyncClock>| {
+        if !inputs.pressed(input) {
+            *held = None;
+            return None;
+        }
+
+        // The frame in which the input is pressed does not count.
+        let held = held.get_or_insert(Duration::ZERO);
+        if inputs.just_pressed(input) {
+            *held = Duration::ZERO;
+        } else {
+            *held += clock.delta();
+        }
+        (duration <= *held).then_some(input)
+    })
+}
+
+
+/// Waits until the inputs of `sequence` are pressed in order within `window`, and outputs them.
+///
+/// The window starts when the first input of the sequence is pressed, and exceeding it starts the sequence over.
+/// Pressing an input that does not continue the sequence falls back to the longest part of the sequence
+/// that the latest inputs still match, so `[A, A, B]` is matched by `A A A B`.
+/// The time is measured with [`AsyncClock`].
+///
+/// Inputs pressed in the same frame are handled in an unspecified order,
+/// so a sequence that repeats or reorders them is only matched reliably when they are pressed in separate frames.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn_async(|schedules| async move {
+///         let hadouken = [KeyCode::Down, KeyCode::Right, KeyCode::P];
+///         schedules.add_system(Update, wait::input_sequence(&hadouken, Duration::from_millis(500))).await;
+///     });
+/// }
+/// ```
+pub fn input_sequence<T>(sequence: &[T], window: Duration) -> impl IntoAsyncScheduleCommand<Vec<T>>
+    where T: Copy + Eq + Hash + Send + Sync + 'static
+{
+    let sequence = sequence.to_vec();
+    let fallback = fallback_table(&sequence);
+    output_as("wait::input_sequence", move |mut progress: Local<(Duration, Vec<Duration>)>, inputs: Res<Input<T>>, clock: Res<AsyncClock>| {
+        if sequence.is_empty() {
+            return Some(Vec::new());
+        }
+
+        // `pressed` holds when each input of the matched part of the sequence was pressed.
+        let (now, pressed) = &mut *progress;
+        *now += clock.delta();
+        if pressed.first().is_some_and(|first| window < *now - *first) {
+            pressed.clear();
+        }
+
+        for input in inputs.get_just_pressed() {
+            let mut matched = pressed.len();
+            while 0 < matched && sequence[matched] != *input {
+                matched = fallback[matched - 1];
+            }
+            if sequence[matched] == *input {
+                matched += 1;
+            }
+
+            // The inputs that still match are the latest ones, followed by this input.
+            let kept = matched.saturating_sub(1);
+            pressed.drain(..pressed.len() - kept);
+            if 0 < matched {
+                pressed.push(*now);
+            }
+
+            if matched == sequence.len() {
+                return Some(sequence.clone());
+            }
+        }
+        None
+    })
+}
+
+
+/// Returns, for each prefix of `sequence`, the length of its longest proper suffix that is also a prefix of `sequence`.
+fn fallback_table<T: Eq>(sequence: &[T]) -> Vec<usize> {
+    let mut table = vec![0; sequence.len()];
+    let mut matched = 0;
+    for i in 1..sequence.len() {
+        while 0 < matched && sequence[i] != sequence[matched] {
+            matched = table[matched - 1];
+        }
+        if sequence[i] == sequence[matched] {
+            matched += 1;
+        }
+        table[i] = matched;
+    }
+    table
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::app::{App, Update};
+    use bevy::input::Input;
+    use bevy::prelude::{KeyCode, MouseButton, ResMut, Resource};
+
+    use crate::clock::{AsyncClock, ManualAsyncClock};
+    use crate::diagnostics::AsyncTaskDiagnostics;
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::{once, wait};
+    use crate::runner::wait::input::fallback_table;
+    use crate::test_util::new_deterministic_app;
+
+    #[derive(Resource, Default)]
+    struct Matched(Vec<KeyCode>);
+
+
+    fn input_app() -> App {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Input<KeyCode>>();
+        app.init_resource::<Input<MouseButton>>();
+        app.init_resource::<Matched>();
+        app
+    }
+
+
+    /// Stands in for the input system: presses the keys for one frame.
+    fn tap(app: &mut App, keys: &[KeyCode]) {
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        for key in keys {
+            input.press(*key);
+        }
+        app.update();
+        let mut input = app.world.resource_mut::<Input<KeyCode>>();
+        input.release_all();
+        input.clear();
+    }
+
+
+    fn push_matched(keys: Vec<KeyCode>) -> impl Fn(ResMut<Matched>) {
+        move |mut matched: ResMut<Matched>| matched.0.extend(keys.iter().copied())
+    }
+
+
+    #[test]
+    fn describe_input_runners_by_constructor() {
+        let mut app = input_app();
+        app.init_resource::<AsyncTaskDiagnostics>();
+        app.spawn_async(|schedules| async move {
+            futures::join!(
+                schedules.add_system(Update, wait::key_pressed(KeyCode::Space)),
+                schedules.add_system(Update, wait::any_key::<KeyCode>()),
+                schedules.add_system(Update, wait::button_held(KeyCode::Space, Duration::from_secs(1))),
+                schedules.add_system(Update, wait::input_sequence(&[KeyCode::A], Duration::from_secs(1))),
+            );
+        });
+
+        app.update();
+        app.update();
+        let kinds = app.world.resource::<AsyncTaskDiagnostics>().tasks()[0]
+            .awaiting
+            .iter()
+            .map(|runner| runner.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["wait::key_pressed", "wait::any_key", "wait::button_held", "wait::input_sequence"]);
+    }
+
+
+    #[test]
+    fn wait_key_pressed() {
+        let mut app = input_app();
+        let task = app.spawn_async(|schedules| async move {
+            let key = schedules.add_system(Update, wait::key_pressed(KeyCode::Space)).await;
+            let button = schedules.add_system(Update, wait::key_pressed(MouseButton::Left)).await;
+            assert_eq!(button, MouseButton::Left);
+            schedules.add_system(Update, once::run(push_matched(vec![key]))).await;
+        }).id();
+
+        app.update();
+        tap(&mut app, &[KeyCode::A]);
+        app.update();
+        assert!(app.world.resource::<Matched>().0.is_empty());
+
+        tap(&mut app, &[KeyCode::Space]);
+        app.update();
+        app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Left);
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Matched>().0, vec![KeyCode::Space]);
+        assert!(app.world.get_entity(task).is_none());
+    }
+
+
+    #[test]
+    fn wait_any_key() {
+        let mut app = input_app();
+        app.spawn_async(|schedules| async move {
+            let key = schedules.add_system(Update, wait::any_key()).await;
+            schedules.add_system(Update, once::run(push_matched(vec![key]))).await;
+        });
+
+        app.update();
+        tap(&mut app, &[KeyCode::Z]);
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Matched>().0, vec![KeyCode::Z]);
+    }
+
+
+    #[test]
+    fn wait_button_held() {
+        let clock = ManualAsyncClock::default();
+        let mut app = input_app();
+        app.insert_resource(AsyncClock::manual(clock.clone()));
+        app.spawn_async(|schedules| async move {
+            let key = schedules.add_system(Update, wait::button_held(KeyCode::E, Duration::from_secs(1))).await;
+            schedules.add_system(Update, once::run(push_matched(vec![key]))).await;
+        });
+        app.update();
+
+        app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::E);
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        clock.advance(Duration::from_millis(600));
+        app.update();
+
+        // released before a second has passed
+        app.world.resource_mut::<Input<KeyCode>>().release(KeyCode::E);
+        clock.advance(Duration::from_millis(600));
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::E);
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        clock.advance(Duration::from_millis(600));
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Matched>().0.is_empty());
+
+        clock.advance(Duration::from_millis(600));
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Matched>().0, vec![KeyCode::E]);
+    }
+
+
+    #[test]
+    fn wait_input_sequence() {
+        let clock = ManualAsyncClock::default();
+        let mut app = input_app();
+        app.insert_resource(AsyncClock::manual(clock.clone()));
+        app.spawn_async(|schedules| async move {
+            let combo = [KeyCode::Down, KeyCode::Right, KeyCode::P];
+            let keys = schedules.add_system(Update, wait::input_sequence(&combo, Duration::from_millis(500))).await;
+            schedules.add_system(Update, once::run(push_matched(keys))).await;
+        });
+        app.update();
+
+        // too slow
+        tap(&mut app, &[KeyCode::Down]);
+        clock.advance(Duration::from_millis(400));
+        tap(&mut app, &[KeyCode::Right]);
+        clock.advance(Duration::from_millis(200));
+        tap(&mut app, &[KeyCode::P]);
+        // wrong key
+        tap(&mut app, &[KeyCode::Down]);
+        tap(&mut app, &[KeyCode::Left]);
+        tap(&mut app, &[KeyCode::P]);
+        app.update();
+        assert!(app.world.resource::<Matched>().0.is_empty());
+
+        tap(&mut app, &[KeyCode::Down]);
+        clock.advance(Duration::from_millis(200));
+        tap(&mut app, &[KeyCode::Right]);
+        clock.advance(Duration::from_millis(200));
+        tap(&mut app, &[KeyCode::P]);
+        app.update();
+        assert_eq!(app.world.resource::<Matched>().0, vec![KeyCode::Down, KeyCode::Right, KeyCode::P]);
+    }
+
+
+    #[test]
+    fn fall_back_to_matching_part_of_sequence() {
+        let mut app = input_app();
+        app.spawn_async(|schedules| async move {
+            let combo = [KeyCode::A, KeyCode::A, KeyCode::B];
+            let keys = schedules.add_system(Update, wait::input_sequence(&combo, Duration::from_secs(1))).await;
+            schedules.add_system(Update, once::run(push_matched(keys))).await;
+        });
+        app.update();
+
+        for key in [KeyCode::A, KeyCode::A, KeyCode::A, KeyCode::B] {
+            tap(&mut app, &[key]);
+        }
+        app.update();
+        assert_eq!(app.world.resource::<Matched>().0, vec![KeyCode::A, KeyCode::A, KeyCode::B]);
+    }
+
+
+    #[test]
+    fn build_fallback_table() {
+        assert_eq!(fallback_table(&[1, 1, 2]), vec![0, 1, 0]);
+        assert_eq!(fallback_table(&[1, 2, 1, 2, 3]), vec![0, 0, 1, 2, 0]);
+        assert_eq!(fallback_table::<u8>(&[]), Vec::<usize>::new());
+    }
+}
@@ -4,7 +4,7 @@ use bevy::prelude::{Event, EventReader, In, IntoSystem, IntoSystemConfigs, Query
 
 use crate::async_schedules::TaskSender;
 use crate::prelude::{AsyncSchedule, AsyncScheduleCommand};
-use crate::runner::{IntoAsyncScheduleCommand, schedule_initialize, task_running};
+use crate::runner::{IntoAsyncScheduleCommand, schedule_initialize, task_running, traced};
 use crate::runner::config::AsyncSystemConfig;
 
 
@@ -37,7 +37,22 @@ pub const fn output<Out, Marker, Sys>(system: Sys) -> impl IntoAsyncScheduleComm
         Marker: Send + Sync + 'static,
         Sys: IntoSystem<(), Option<Out>, Marker> + Send + Sync + 'static
 {
-    WaitOutput(AsyncSystemConfig::new(system))
+    output_as("wait::output", system)
+}
+
+
+/// Same as [`output`], but the runner is described as `kind` in [`AsyncRunnerInfo`](crate::diagnostics::AsyncRunnerInfo).
+#[inline(always)]
+pub(crate) const fn output_as<Out, Marker, Sys>(kind: &'static str, system: Sys) -> impl IntoAsyncScheduleCommand<Out>
+    where
+        Out: Send + Sync + 'static,
+        Marker: Send + Sync + 'static,
+        Sys: IntoSystem<(), Option<Out>, Marker> + Send + Sync + 'static
+{
+    WaitOutput {
+        config: AsyncSystemConfig::new(system),
+        kind,
+    }
 }
 
 
@@ -52,7 +67,10 @@ pub fn output_event<E: Event + Clone>() -> impl IntoAsyncScheduleCommand<E> {
 }
 
 
-struct WaitOutput<Out, Marker, Sys>(AsyncSystemConfig<Option<Out>, Marker, Sys>);
+struct WaitOutput<Out, Marker, Sys> {
+    config: AsyncSystemConfig<Option<Out>, Marker, Sys>,
+    kind: &'static str,
+}
 
 
 impl<Out, Marker, Sys> IntoAsyncScheduleCommand<Out> for WaitOutput<Out, Marker, Sys>
@@ -65,8 +83,9 @@ impl<Out, Marker, Sys> IntoAsyncScheduleCommand<Out> for WaitOutput<Out, Marker,
     fn into_schedule_command(self, sender: TaskSender<Out>, schedule_label: impl ScheduleLabel + Clone) -> AsyncScheduleCommand {
         AsyncScheduleCommand::new(Executor {
             sender,
-            config: self.0,
+            config: self.config,
             schedule_label,
+            kind: self.kind,
         })
     }
 }
@@ -76,6 +95,7 @@ struct Executor<Out, Marker, Sys, Label> {
     sender: TaskSender<Out>,
     config: AsyncSystemConfig<Option<Out>, Marker, Sys>,
     schedule_label: Label,
+    kind: &'static str,
 }
 
 
@@ -91,7 +111,7 @@ impl<Out, Marker, Sys, Label> AsyncSchedule for Executor<Out, Marker, Sys, Label
         entity_commands.insert(self.sender);
         let entity = entity_commands.id();
 
-        schedule.add_systems(self
+        schedule.add_systems(traced(entity, self
             .config
             .system
             .pipe(move |In(input): In<Option<Out>>, mut senders: Query<&mut TaskSender<Out>>| {
@@ -99,10 +119,19 @@ impl<Out, Marker, Sys, Label> AsyncSchedule for Executor<Out, Marker, Sys, Label
                 let Ok(mut sender) = senders.get_mut(entity) else { return; };
                 let _ = sender.try_send(input);
                 sender.close_channel();
-            })
+            }))
             .run_if(task_running::<Out>(entity))
         );
     }
+
+    fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+
+    fn system_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<Sys>())
+    }
 }
 
 
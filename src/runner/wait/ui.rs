@@ -0,0 +1,214 @@
+use bevy::prelude::{Entity, Local, Query};
+use bevy::ui::Interaction;
+use bevy::utils::HashMap;
+
+use crate::runner::IntoAsyncScheduleCommand;
+use crate::runner::wait::{output_as, until_as};
+
+/// Waits until the [`Interaction`] of `entity` becomes `interaction`.
+///
+/// It resolves immediately if the entity already has that interaction.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let button = commands.spawn(ButtonBundle::default()).id();
+///     commands.spawn_async(move |schedules| async move {
+///         schedules.add_system(Update, wait::interaction(button, Interaction::Hovered)).await;
+///         println!("hovered");
+///     });
+/// }
+/// ```
+#[inline]
+pub fn interaction(entity: Entity, interaction: Interaction) -> impl IntoAsyncScheduleCommand {
+    until_as("wait::interaction", move |interactions: Query<&Interaction>| {
+        interactions.get(entity).is_ok_and(|current| *current == interaction)
+    })
+}
+
+
+/// Waits until the button `entity` is clicked.
+///
+/// A click is a change of [`Interaction`] to [`Interaction::Pressed`] after the system has started,
+/// so a button that is already held down when the system starts must be pressed again.
+/// It keeps waiting while the entity does not exist.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let ok = commands.spawn(ButtonBundle::default()).id();
+///     commands.spawn_async(move |schedules| async move {
+///         schedules.add_system(Update, wait::button_clicked(ok)).await;
+///         println!("OK");
+///     });
+/// }
+/// ```
+#[inline]
+pub fn button_clicked(entity: Entity) -> impl IntoAsyncScheduleCommand {
+    until_as("wait::button_clicked", move |mut previous: Local<HashMap<Entity, Interaction>>, interactions: Query<&Interaction>| {
+        find_clicked(&[entity], &mut previous, &interactions).is_some()
+    })
+}
+
+
+/// Waits until any of the `buttons` is clicked and outputs the clicked one.
+///
+/// See [`button_clicked`] for what counts as a click.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let ok = commands.spawn(ButtonBundle::default()).id();
+///     let cancel = commands.spawn(ButtonBundle::default()).id();
+///     commands.spawn_async(move |schedules| async move {
+///         let clicked = schedules.add_system(Update, wait::any_button_clicked(&[ok, cancel])).await;
+///         if clicked == ok {
+///             println!("OK");
+///         }
+///     });
+/// }
+/// ```
+pub fn any_button_clicked(buttons: &[Entity]) -> impl IntoAsyncScheduleCommand<Entity> {
+    let buttons = buttons.to_vec();
+    output_as("wait::any_button_clicked", move |mut previous: Local<HashMap<Entity, Interaction>>, interactions: Query<&Interaction>| {
+        find_clicked(&buttons, &mut previous, &interactions)
+    })
+}
+
+
+/// Records the interaction of each button and returns the first one that has just become pressed.
+fn find_clicked(
+    buttons: &[Entity],
+    previous: &mut HashMap<Entity, Interaction>,
+    interactions: &Query<&Interaction>,
+) -> Option<Entity> {
+    let mut clicked = None;
+    for button in buttons.iter().copied() {
+        let Ok(interaction) = interactions.get(button) else { continue; };
+        let was = previous.insert(button, *interaction);
+        if clicked.is_none()
+            && *interaction == Interaction::Pressed
+            && was.is_some_and(|was| was != Interaction::Pressed) {
+            clicked = Some(button);
+        }
+    }
+    clicked
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::prelude::{Entity, ResMut, Resource};
+    use bevy::ui::Interaction;
+
+    use crate::diagnostics::AsyncTaskDiagnostics;
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::{once, wait};
+    use crate::test_util::new_deterministic_app;
+
+    #[derive(Resource, Default)]
+    struct Clicked(Vec<Entity>);
+
+
+    fn push_clicked(entity: Entity) -> impl Fn(ResMut<Clicked>) {
+        move |mut clicked: ResMut<Clicked>| clicked.0.push(entity)
+    }
+
+
+    fn set_interaction(app: &mut App, entity: Entity, interaction: Interaction) {
+        *app.world.get_mut::<Interaction>(entity).unwrap() = interaction;
+        app.update();
+    }
+
+
+    #[test]
+    fn describe_ui_waits_by_constructor() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<AsyncTaskDiagnostics>();
+        let button = app.world.spawn(Interaction::None).id();
+        app.spawn_async(move |schedules| async move {
+            futures::join!(
+                schedules.add_system(Update, wait::interaction(button, Interaction::Hovered)),
+                schedules.add_system(Update, wait::button_clicked(button)),
+                schedules.add_system(Update, wait::any_button_clicked(&[button])),
+            );
+        });
+
+        app.update();
+        app.update();
+        let kinds = app.world.resource::<AsyncTaskDiagnostics>().tasks()[0]
+            .awaiting
+            .iter()
+            .map(|runner| runner.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["wait::interaction", "wait::button_clicked", "wait::any_button_clicked"]);
+    }
+
+
+    #[test]
+    fn wait_interaction() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Clicked>();
+        let button = app.world.spawn(Interaction::None).id();
+        app.spawn_async(move |schedules| async move {
+            schedules.add_system(Update, wait::interaction(button, Interaction::Hovered)).await;
+            schedules.add_system(Update, once::run(push_clicked(button))).await;
+        });
+
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Clicked>().0.is_empty());
+
+        set_interaction(&mut app, button, Interaction::Hovered);
+        app.update();
+        assert_eq!(app.world.resource::<Clicked>().0, vec![button]);
+    }
+
+
+    #[test]
+    fn wait_button_clicked() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Clicked>();
+        let button = app.world.spawn(Interaction::Pressed).id();
+        app.spawn_async(move |schedules| async move {
+            schedules.add_system(Update, wait::button_clicked(button)).await;
+            schedules.add_system(Update, once::run(push_clicked(button))).await;
+        });
+
+        // held down since before the system started
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Clicked>().0.is_empty());
+
+        set_interaction(&mut app, button, Interaction::Hovered);
+        set_interaction(&mut app, button, Interaction::Pressed);
+        app.update();
+        assert_eq!(app.world.resource::<Clicked>().0, vec![button]);
+    }
+
+
+    #[test]
+    fn wait_any_button_clicked() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Clicked>();
+        let ok = app.world.spawn(Interaction::None).id();
+        let cancel = app.world.spawn(Interaction::None).id();
+        app.spawn_async(move |schedules| async move {
+            let clicked = schedules.add_system(Update, wait::any_button_clicked(&[ok, cancel])).await;
+            schedules.add_system(Update, once::run(push_clicked(clicked))).await;
+        });
+
+        app.update();
+        set_interaction(&mut app, ok, Interaction::Hovered);
+        set_interaction(&mut app, cancel, Interaction::Pressed);
+        app.update();
+        assert_eq!(app.world.resource::<Clicked>().0, vec![cancel]);
+    }
+}
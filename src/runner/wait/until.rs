@@ -3,7 +3,7 @@ use bevy::ecs::system::EntityCommands;
 use bevy::prelude::{Commands, Entity, Event, EventReader, In, IntoSystem, IntoSystemConfigs, Query, Schedules};
 
 use crate::async_schedules::TaskSender;
-use crate::runner::{AsyncSchedule, AsyncScheduleCommand, IntoAsyncScheduleCommand, schedule_initialize, task_running};
+use crate::runner::{AsyncSchedule, AsyncScheduleCommand, IntoAsyncScheduleCommand, schedule_initialize, task_running, traced};
 use crate::runner::config::AsyncSystemConfig;
 
 
@@ -31,7 +31,21 @@ pub const fn until<Marker, Sys>(system: Sys) -> impl IntoAsyncScheduleCommand<()
         Marker: Send + Sync + 'static,
         Sys: IntoSystem<(), bool, Marker> + Send + Sync + 'static
 {
-    Until(AsyncSystemConfig::new(system))
+    until_as("wait::until", system)
+}
+
+
+/// Same as [`until`], but the runner is described as `kind` in [`AsyncRunnerInfo`](crate::diagnostics::AsyncRunnerInfo).
+#[inline(always)]
+pub(crate) const fn until_as<Marker, Sys>(kind: &'static str, system: Sys) -> impl IntoAsyncScheduleCommand<()>
+    where
+        Marker: Send + Sync + 'static,
+        Sys: IntoSystem<(), bool, Marker> + Send + Sync + 'static
+{
+    Until {
+        config: AsyncSystemConfig::new(system),
+        kind,
+    }
 }
 
 
@@ -46,7 +60,10 @@ pub fn until_event<E: Event>() -> impl IntoAsyncScheduleCommand<()> {
 }
 
 
-struct Until<Marker, Sys>(AsyncSystemConfig<bool, Marker, Sys>);
+struct Until<Marker, Sys> {
+    config: AsyncSystemConfig<bool, Marker, Sys>,
+    kind: &'static str,
+}
 
 
 impl<Marker, Sys> IntoAsyncScheduleCommand<()> for Until<Marker, Sys>
@@ -58,8 +75,9 @@ impl<Marker, Sys> IntoAsyncScheduleCommand<()> for Until<Marker, Sys>
     fn into_schedule_command(self, sender: TaskSender<()>, schedule_label: impl ScheduleLabel + Clone) -> AsyncScheduleCommand {
         AsyncScheduleCommand::new(Scheduler {
             sender,
-            config: self.0,
+            config: self.config,
             schedule_label,
+            kind: self.kind,
         })
     }
 }
@@ -69,6 +87,7 @@ struct Scheduler<Marker, Sys, Label> {
     sender: TaskSender<()>,
     config: AsyncSystemConfig<bool, Marker, Sys>,
     schedule_label: Label,
+    kind: &'static str,
 }
 
 
@@ -83,7 +102,7 @@ impl<Marker, Sys, Label> AsyncSchedule for Scheduler<Marker, Sys, Label>
         entity_commands.insert(self.sender);
         let entity = entity_commands.id();
 
-        schedule.add_systems(self
+        schedule.add_systems(traced(entity, self
             .config
             .system
             .pipe(move |In(finished): In<bool>, mut commands: Commands, mut senders: Query<(Entity, &mut TaskSender<()>)>| {
@@ -94,10 +113,19 @@ impl<Marker, Sys, Label> AsyncSchedule for Scheduler<Marker, Sys, Label>
                 let _ = sender.try_send(());
                 sender.close_channel();
                 commands.entity(entity).remove::<TaskSender<()>>();
-            })
+            }))
             .run_if(task_running::<()>(entity))
         );
     }
+
+    fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+
+    fn system_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<Sys>())
+    }
 }
 
 
@@ -0,0 +1,350 @@
+//! UI-agnostic primitives for dialogue and cutscene scripts.
+//!
+//! [`Script`] sends a request event such as [`ScriptSay`] and waits for the response event, such as [`ScriptAdvance`],
+//! that carries the same [`ScriptId`].
+//! Both are handled by one system, so a response sent in the same frame as the request is not missed.
+//! The UI reads the request events, shows them in any way, and sends the response when the player acts.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_async_system::prelude::*;
+//! use bevy_async_system::script::{Script, ScriptAdvance, ScriptSay};
+//!
+//! fn start_dialogue(mut commands: Commands) {
+//!     commands.spawn_async(|schedules| async move {
+//!         let script = Script::new(schedules);
+//!         script.say_as("Alice", "Do you want to open the door?").await;
+//!         if script.choice(["Yes", "No"]).await == 0 {
+//!             script.say("The door opens.").await;
+//!         }
+//!     });
+//! }
+//!
+//! fn show_lines(
+//!     mut lines: EventReader<ScriptSay>,
+//!     mut current: Local<Option<ScriptSay>>,
+//!     mut advance: EventWriter<ScriptAdvance>,
+//!     mouse: Res<Input<MouseButton>>,
+//! ) {
+//!     if let Some(line) = lines.iter().last() {
+//!         println!("{}", line.text);
+//!         *current = Some(line.clone());
+//!     }
+//!     if mouse.just_pressed(MouseButton::Left) {
+//!         if let Some(line) = current.take() {
+//!             advance.send(line.advance());
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy::app::{App, Update};
+use bevy::ecs::schedule::{BoxedScheduleLabel, ScheduleLabel};
+use bevy::prelude::{Event, EventReader, EventWriter};
+
+use crate::async_schedules::AsyncSchedules;
+use crate::runner::wait;
+
+/// Identifies a request of [`Script`] so that the response can be matched to it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ScriptId(u64);
+
+
+impl ScriptId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+
+/// Sent by [`Script::say`] to show a line; answer with [`ScriptSay::advance`].
+#[derive(Event, Debug, Clone, Eq, PartialEq)]
+pub struct ScriptSay {
+    pub id: ScriptId,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+
+impl ScriptSay {
+    /// Creates the response that lets the script continue.
+    #[inline]
+    pub const fn advance(&self) -> ScriptAdvance {
+        ScriptAdvance(self.id)
+    }
+}
+
+
+/// Sent by [`Script::choice`] to show options; answer with [`ScriptChoice::choose`].
+#[derive(Event, Debug, Clone, Eq, PartialEq)]
+pub struct ScriptChoice {
+    pub id: ScriptId,
+    pub options: Vec<String>,
+}
+
+
+impl ScriptChoice {
+    /// Creates the response that selects the option at `index`.
+    ///
+    /// Responses with an index out of range are ignored by the script.
+    #[inline]
+    pub const fn choose(&self, index: usize) -> ScriptChosen {
+        ScriptChosen {
+            id: self.id,
+            index,
+        }
+    }
+}
+
+
+/// Sent by [`Script::wait_for_input`]; answer with [`ScriptWaitForInput::advance`].
+#[derive(Event, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScriptWaitForInput(pub ScriptId);
+
+
+impl ScriptWaitForInput {
+    /// Creates the response that lets the script continue.
+    #[inline]
+    pub const fn advance(&self) -> ScriptAdvance {
+        ScriptAdvance(self.0)
+    }
+}
+
+
+/// The response to [`ScriptSay`] and [`ScriptWaitForInput`].
+#[derive(Event, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScriptAdvance(pub ScriptId);
+
+
+/// The response to [`ScriptChoice`].
+#[derive(Event, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScriptChosen {
+    pub id: ScriptId,
+    pub index: usize,
+}
+
+
+/// Runs dialogue primitives in a task.
+///
+/// The systems that send requests and wait for responses are added to [`Update`] by default.
+#[derive(Clone)]
+pub struct Script {
+    schedules: AsyncSchedules,
+    schedule_label: BoxedScheduleLabel,
+}
+
+
+impl Script {
+    /// Creates a script whose systems run in [`Update`].
+    #[inline]
+    pub fn new(schedules: AsyncSchedules) -> Self {
+        Self::with_schedule(schedules, Update)
+    }
+
+
+    /// Creates a script whose systems run in `schedule_label`.
+    #[inline]
+    pub fn with_schedule(schedules: AsyncSchedules, schedule_label: impl ScheduleLabel) -> Self {
+        Self {
+            schedules,
+            schedule_label: Box::new(schedule_label),
+        }
+    }
+
+
+    /// Shows `text` without a speaker and waits until the line is advanced.
+    pub async fn say(&self, text: impl Into<String>) {
+        self.say_line(None, text.into()).await;
+    }
+
+
+    /// Shows `text` spoken by `speaker` and waits until the line is advanced.
+    pub async fn say_as(&self, speaker: impl Into<String>, text: impl Into<String>) {
+        self.say_line(Some(speaker.into()), text.into()).await;
+    }
+
+
+    /// Shows the options and returns the index of the chosen one.
+    pub async fn choice<S: Into<String>>(&self, options: impl IntoIterator<Item=S>) -> usize {
+        let id = ScriptId::next();
+        let options = options.into_iter().map(Into::into).collect::<Vec<_>>();
+        let len = options.len();
+        self.request(ScriptChoice { id, options }, move |chosen: &ScriptChosen| {
+            (chosen.id == id && chosen.index < len).then_some(chosen.index)
+        }).await
+    }
+
+
+    /// Waits until the player gives any input, for example between the cuts of a cutscene.
+    pub async fn wait_for_input(&self) {
+        let id = ScriptId::next();
+        self.request(ScriptWaitForInput(id), advanced(id)).await;
+    }
+
+
+    async fn say_line(&self, speaker: Option<String>, text: String) {
+        let id = ScriptId::next();
+        self.request(ScriptSay { id, speaker, text }, advanced(id)).await;
+    }
+
+
+    /// Sends `request` on the first run of the system, then outputs the first response accepted by `accept`.
+    ///
+    /// The request and the response are handled by one system,
+    /// so the response cannot be sent before the system that reads it exists.
+    /// Responses are matched by id, since events sent for an earlier request may still be readable.
+    async fn request<Req, Res, Out>(&self, request: Req, accept: impl Fn(&Res) -> Option<Out> + Send + Sync + 'static) -> Out
+        where
+            Req: Event,
+            Res: Event,
+            Out: Send + Sync + 'static
+    {
+        let mut request = Some(request);
+        self.schedules.add_system(self.schedule_label.clone(), wait::output(move |mut requests: EventWriter<Req>, mut responses: EventReader<Res>| {
+            if let Some(request) = request.take() {
+                requests.send(request);
+            }
+            responses.iter().find_map(&accept)
+        })).await
+    }
+}
+
+
+fn advanced(id: ScriptId) -> impl Fn(&ScriptAdvance) -> Option<()> + Send + Sync + 'static {
+    move |advance| (advance.0 == id).then_some(())
+}
+
+
+pub(crate) fn add_script_events(app: &mut App) {
+    app
+        .add_event::<ScriptSay>()
+        .add_event::<ScriptChoice>()
+        .add_event::<ScriptWaitForInput>()
+        .add_event::<ScriptAdvance>()
+        .add_event::<ScriptChosen>();
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::prelude::{EventReader, EventWriter, ResMut, Resource};
+
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::once;
+    use crate::script::{Script, ScriptAdvance, ScriptChoice, ScriptChosen, ScriptSay, ScriptWaitForInput};
+    use crate::test_util::{new_app, new_deterministic_app};
+
+    #[derive(Resource, Default)]
+    struct Shown(Vec<String>);
+
+
+    /// Stands in for the UI: shows each request and answers it in the same frame.
+    fn answer(
+        mut shown: ResMut<Shown>,
+        mut lines: EventReader<ScriptSay>,
+        mut choices: EventReader<ScriptChoice>,
+        mut inputs: EventReader<ScriptWaitForInput>,
+        mut advance: EventWriter<ScriptAdvance>,
+        mut chosen: EventWriter<ScriptChosen>,
+    ) {
+        for line in lines.iter() {
+            shown.0.push(format!("{}: {}", line.speaker.as_deref().unwrap_or("-"), line.text));
+            advance.send(line.advance());
+        }
+        for choice in choices.iter() {
+            shown.0.push(choice.options.join("/"));
+            chosen.send(choice.choose(1));
+        }
+        for input in inputs.iter() {
+            shown.0.push("input".to_string());
+            advance.send(input.advance());
+        }
+    }
+
+
+    fn spawn_dialogue(app: &mut App) {
+        app.init_resource::<Shown>();
+        app.add_systems(Update, answer);
+        app.spawn_async(|schedules| async move {
+            let script = Script::new(schedules.clone());
+            script.say_as("Alice", "Open the door?").await;
+            let index = script.choice(["Yes", "No"]).await;
+            script.say(format!("chose {index}")).await;
+            script.say("bye").await;
+            script.wait_for_input().await;
+            schedules.add_system(Update, once::run(|mut shown: ResMut<Shown>| shown.0.push("end".to_string()))).await;
+        });
+    }
+
+
+    const DIALOGUE: [&str; 6] = [
+        "Alice: Open the door?",
+        "Yes/No",
+        "-: chose 1",
+        "-: bye",
+        "input",
+        "end",
+    ];
+
+
+    #[test]
+    fn run_dialogue() {
+        let mut app = new_deterministic_app();
+        spawn_dialogue(&mut app);
+
+        for _ in 0..30 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Shown>().0, DIALOGUE);
+    }
+
+
+    #[test]
+    fn run_dialogue_on_task_pool() {
+        let mut app = new_app();
+        spawn_dialogue(&mut app);
+
+        for _ in 0..1000 {
+            app.update();
+            if app.world.resource::<Shown>().0.len() == DIALOGUE.len() {
+                break;
+            }
+            std::thread::yield_now();
+        }
+        assert_eq!(app.world.resource::<Shown>().0, DIALOGUE);
+    }
+
+
+    #[derive(Resource, Default)]
+    struct LastLine(Option<ScriptSay>);
+
+
+    #[test]
+    fn wait_until_advanced() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<LastLine>();
+        app.add_systems(Update, |mut lines: EventReader<ScriptSay>, mut last: ResMut<LastLine>| {
+            if let Some(line) = lines.iter().last() {
+                last.0 = Some(line.clone());
+            }
+        });
+        let task = app.spawn_async(|schedules| async move {
+            Script::new(schedules).say("hello").await;
+        }).id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(app.world.get_entity(task).is_some());
+
+        let advance = app.world.resource::<LastLine>().0.as_ref().unwrap().advance();
+        app.world.send_event(advance);
+        app.update();
+        app.update();
+        assert!(app.world.get_entity(task).is_none());
+    }
+}
@@ -0,0 +1,381 @@
+use std::time::Duration;
+
+use bevy::ecs::system::Commands;
+use bevy::hierarchy::DespawnRecursiveExt;
+use bevy::log::{error, warn};
+use bevy::prelude::{Component, Entity, Event, EventWriter, Query, Res};
+
+use crate::async_schedules::{TaskHandle, TaskOutcome};
+use crate::clock::AsyncClock;
+use crate::restart::RestartableTask;
+
+/// Determines whether a supervised task is restarted when it ends.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RestartPolicy {
+    /// The task is never restarted.
+    Never,
+
+    /// The task is restarted only if it panicked.
+    #[default]
+    OnPanic,
+
+    /// The task is restarted whenever it ends.
+    Always,
+}
+
+
+impl RestartPolicy {
+    #[inline]
+    fn should_restart(&self, outcome: &TaskOutcome) -> bool {
+        match self {
+            Self::Never => false,
+            Self::OnPanic => matches!(outcome, TaskOutcome::Panicked(_)),
+            Self::Always => true
+        }
+    }
+}
+
+
+/// How long a supervised task waits before it is restarted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Backoff {
+    /// Waits for the number of frames.
+    Frames(u32),
+
+    /// Waits until the duration has elapsed on [`AsyncClock`].
+    Duration(Duration),
+}
+
+
+/// Supervises a task spawned by
+/// [`spawn_async_restartable`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_restartable)
+/// or [`spawn_async_restartable_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_restartable_named).
+///
+/// When the task ends, it is restarted on the same entity according to [`RestartPolicy`];
+/// the systems it was awaiting are despawned first.
+/// Once the restarts reach [`Supervisor::with_max_restarts`], the task entity is despawned and [`SupervisedTaskGaveUp`] is sent.
+///
+/// A task spawned by another method cannot be restarted;
+/// a warning is logged and the task gives up the first time the policy asks for a restart.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn start_autosave(mut commands: Commands) {
+///     commands
+///         .spawn_async_restartable(|schedules| async move {
+///             loop {
+///                 schedules.add_system(Update, delay::frames(600)).await;
+///                 schedules.add_system(Update, once::run(|| println!("saved"))).await;
+///             }
+///         })
+///         .insert(Supervisor::new(RestartPolicy::OnPanic)
+///             .with_max_restarts(5)
+///             .with_backoff(Backoff::Frames(60)));
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct Supervisor {
+    policy: RestartPolicy,
+    max_restarts: Option<u32>,
+    backoff: Option<Backoff>,
+    restarts: u32,
+    last_outcome: Option<TaskOutcome>,
+}
+
+
+impl Supervisor {
+    /// Creates a supervisor that restarts the task without limit and without waiting.
+    #[inline]
+    pub const fn new(policy: RestartPolicy) -> Self {
+        Self {
+            policy,
+            max_restarts: None,
+            backoff: None,
+            restarts: 0,
+            last_outcome: None,
+        }
+    }
+
+
+    /// Gives up after the task has been restarted `max_restarts` times.
+    #[inline]
+    pub const fn with_max_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = Some(max_restarts);
+        self
+    }
+
+
+    /// Waits for `backoff` before each restart.
+    #[inline]
+    pub const fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
+
+
+    /// Returns how many times the task has been restarted.
+    #[inline]
+    pub const fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+
+    /// Returns how the task ended the last time, if it has ended.
+    #[inline]
+    pub const fn last_outcome(&self) -> Option<&TaskOutcome> {
+        self.last_outcome.as_ref()
+    }
+}
+
+
+impl Default for Supervisor {
+    #[inline]
+    fn default() -> Self {
+        Self::new(RestartPolicy::default())
+    }
+}
+
+
+/// Sent when a supervised task should be restarted but has reached the max restart count.
+///
+/// The task entity has been despawned.
+#[derive(Event, Debug, Clone, Eq, PartialEq)]
+pub struct SupervisedTaskGaveUp {
+    /// The entity of the task.
+    pub task: Entity,
+
+    /// How the task ended the last time.
+    pub outcome: TaskOutcome,
+
+    /// How many times the task has been restarted.
+    pub restarts: u32,
+}
+
+
+/// Attached to a supervised task entity waiting for [`Backoff`].
+#[derive(Component)]
+pub(crate) struct PendingRestart(Backoff);
+
+
+/// Despawns the finished task, or restarts it if it is supervised.
+///
+/// The panic of a task without a [`Supervisor`] is resumed, so it propagates as if the task had been polled directly.
+pub(crate) fn finish_task(
+    commands: &mut Commands,
+    task: Entity,
+    outcome: TaskOutcome,
+    supervisors: &mut Query<(&mut Supervisor, Option<&RestartableTask>)>,
+    gave_up: &mut EventWriter<SupervisedTaskGaveUp>,
+) {
+    if let TaskOutcome::Panicked(message) = &outcome {
+        if !supervisors.contains(task) {
+            std::panic::resume_unwind(Box::new(message.clone()));
+        }
+        error!("The async task {task:?} panicked: {message}");
+    }
+
+    if let Ok((mut supervisor, restartable)) = supervisors.get_mut(task) {
+        supervisor.last_outcome = Some(outcome.clone());
+        if supervisor.policy.should_restart(&outcome) {
+            let can_restart = match supervisor.max_restarts {
+                Some(max) => supervisor.restarts < max,
+                None => true
+            };
+            if let Some(restartable) = restartable.filter(|_| can_restart) {
+                supervisor.restarts += 1;
+                let mut entity_commands = commands.entity(task);
+                entity_commands
+                    .despawn_descendants()
+                    .remove::<TaskHandle>();
+                match supervisor.backoff {
+                    Some(backoff) => entity_commands.insert(PendingRestart(backoff)),
+                    None => entity_commands.insert(restartable.task_bundle())
+                };
+                return;
+            }
+            if restartable.is_none() {
+                warn!("The async task {task:?} has a Supervisor but cannot be restarted; spawn it with spawn_async_restartable.");
+            }
+
+            gave_up.send(SupervisedTaskGaveUp {
+                task,
+                outcome,
+                restarts: supervisor.restarts,
+            });
+        }
+    }
+
+    commands.entity(task).despawn_recursive();
+}
+
+
+pub(crate) fn tick_pending_restarts(
+    mut commands: Commands,
+    clock: Res<AsyncClock>,
+    mut pending: Query<(Entity, &mut PendingRestart, &RestartableTask)>,
+) {
+    for (entity, mut pending, restartable) in pending.iter_mut() {
+        let ready = match &mut pending.0 {
+            Backoff::Frames(frames) => {
+                let ready = *frames == 0;
+                *frames = frames.saturating_sub(1);
+                ready
+            }
+            Backoff::Duration(duration) => {
+                *duration = duration.saturating_sub(clock.delta());
+                duration.is_zero()
+            }
+        };
+        if ready {
+            commands
+                .entity(entity)
+                .remove::<PendingRestart>()
+                .insert(restartable.task_bundle());
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use bevy::app::Update;
+    use bevy::ecs::event::ManualEventReader;
+    use bevy::prelude::Events;
+
+    use crate::async_schedules::TaskOutcome;
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::once;
+    use crate::supervisor::{Backoff, RestartPolicy, SupervisedTaskGaveUp, Supervisor};
+    use crate::test_util::new_deterministic_app;
+
+    #[test]
+    fn restart_on_panic_until_max_restarts() {
+        let mut app = new_deterministic_app();
+        let started = Arc::new(AtomicU32::new(0));
+        let counter = started.clone();
+        let task = app
+            .spawn_async_restartable(move |schedules| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    schedules.add_system(Update, once::run(|| {})).await;
+                    panic!("broken");
+                }
+            })
+            .insert(Supervisor::new(RestartPolicy::OnPanic).with_max_restarts(2))
+            .id();
+
+        for _ in 0..10 {
+            app.update();
+            if app.world.get_entity(task).is_none() {
+                break;
+            }
+        }
+        assert_eq!(started.load(Ordering::Relaxed), 3);
+        assert!(app.world.get_entity(task).is_none());
+
+        let events = app.world.resource::<Events<SupervisedTaskGaveUp>>();
+        let gave_up = ManualEventReader::default().iter(events).cloned().collect::<Vec<_>>();
+        assert_eq!(gave_up, vec![SupervisedTaskGaveUp {
+            task,
+            outcome: TaskOutcome::Panicked("broken".to_string()),
+            restarts: 2,
+        }]);
+    }
+
+
+    #[test]
+    fn give_up_if_task_is_not_restartable() {
+        let mut app = new_deterministic_app();
+        let task = app
+            .spawn_async(|schedules| async move {
+                schedules.add_system(Update, once::run(|| {})).await;
+                panic!("broken");
+            })
+            .insert(Supervisor::new(RestartPolicy::OnPanic))
+            .id();
+
+        for _ in 0..5 {
+            app.update();
+            if app.world.get_entity(task).is_none() {
+                break;
+            }
+        }
+        assert!(app.world.get_entity(task).is_none());
+
+        let events = app.world.resource::<Events<SupervisedTaskGaveUp>>();
+        let gave_up = ManualEventReader::default().iter(events).cloned().collect::<Vec<_>>();
+        assert_eq!(gave_up, vec![SupervisedTaskGaveUp {
+            task,
+            outcome: TaskOutcome::Panicked("broken".to_string()),
+            restarts: 0,
+        }]);
+    }
+
+
+    #[test]
+    fn do_not_restart_completed_task_on_panic_policy() {
+        let mut app = new_deterministic_app();
+        let task = app
+            .spawn_async_restartable(|schedules| async move {
+                schedules.add_system(Update, once::run(|| {})).await;
+            })
+            .insert(Supervisor::new(RestartPolicy::OnPanic))
+            .id();
+
+        app.update();
+        app.update();
+        assert!(app.world.get_entity(task).is_none());
+        assert!(app.world.resource::<Events<SupervisedTaskGaveUp>>().is_empty());
+    }
+
+
+    #[test]
+    #[should_panic(expected = "broken")]
+    fn propagate_panic_without_supervisor() {
+        let mut app = new_deterministic_app();
+        app.spawn_async(|schedules| async move {
+            schedules.add_system(Update, once::run(|| {})).await;
+            panic!("broken");
+        });
+
+        for _ in 0..10 {
+            app.update();
+        }
+    }
+
+
+    #[test]
+    fn restart_always_after_backoff() {
+        let mut app = new_deterministic_app();
+        let started = Arc::new(AtomicU32::new(0));
+        let counter = started.clone();
+        let task = app
+            .spawn_async_restartable(move |schedules| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    schedules.add_system(Update, once::run(|| {})).await;
+                }
+            })
+            .insert(Supervisor::new(RestartPolicy::Always).with_backoff(Backoff::Frames(2)))
+            .id();
+
+        // run `once::run` and finish
+        app.update();
+        app.update();
+        // backoff
+        app.update();
+        app.update();
+        assert_eq!(started.load(Ordering::Relaxed), 1);
+
+        app.update();
+        assert_eq!(started.load(Ordering::Relaxed), 2);
+        let supervisor = app.world.get::<Supervisor>(task).unwrap();
+        assert_eq!(supervisor.restarts(), 1);
+        assert_eq!(supervisor.last_outcome(), Some(&TaskOutcome::Completed));
+    }
+}
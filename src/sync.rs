@@ -0,0 +1,266 @@
+use std::any::TypeId;
+use std::future::Future;
+use std::marker::PhantomData;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::{Entity, Resource, Schedules, World};
+use bevy::utils::HashMap;
+
+use crate::async_schedules::{AsyncSchedules, TaskSender};
+use crate::runner::{AsyncSchedule, AsyncScheduleCommand};
+
+/// A counting semaphore shared between async tasks.
+///
+/// Cloning it returns a handle to the same semaphore.
+/// Insert it as a resource or into [`AsyncLocks`] so that tasks and systems can reach it.
+#[derive(Resource, Clone)]
+pub struct AsyncSemaphore {
+    tx: async_channel::Sender<()>,
+    rx: async_channel::Receiver<()>,
+    permits: usize,
+}
+
+
+impl AsyncSemaphore {
+    /// Creates a semaphore with `permits` permits.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `permits` is zero.
+    pub fn new(permits: usize) -> Self {
+        assert!(0 < permits, "a semaphore must have at least one permit");
+        let (tx, rx) = async_channel::bounded(permits);
+        for _ in 0..permits {
+            let _ = tx.try_send(());
+        }
+        Self {
+            tx,
+            rx,
+            permits,
+        }
+    }
+
+
+    /// Waits for a permit.
+    ///
+    /// The permit is returned when [`AsyncSemaphorePermit`] is dropped,
+    /// which also happens when the task holding it is cancelled.
+    pub async fn acquire(&self) -> AsyncSemaphorePermit {
+        // `tx` is held by the semaphore, so the channel is never closed.
+        let _ = self.rx.recv().await;
+        AsyncSemaphorePermit(self.tx.clone())
+    }
+
+
+    /// Takes a permit if one is available, without waiting.
+    pub fn try_acquire(&self) -> Option<AsyncSemaphorePermit> {
+        self.rx.try_recv().ok()?;
+        Some(AsyncSemaphorePermit(self.tx.clone()))
+    }
+
+
+    /// Returns the number of permits available.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.rx.len()
+    }
+
+
+    /// Returns the number of permits the semaphore was created with.
+    #[inline]
+    pub const fn permits(&self) -> usize {
+        self.permits
+    }
+}
+
+
+/// A permit of [`AsyncSemaphore`], returned to the semaphore on drop.
+pub struct AsyncSemaphorePermit(async_channel::Sender<()>);
+
+
+impl Drop for AsyncSemaphorePermit {
+    fn drop(&mut self) {
+        let _ = self.0.try_send(());
+    }
+}
+
+
+/// Holds the semaphores used by [`AsyncSchedules::lock`], keyed by type.
+///
+/// A semaphore with one permit is created the first time a type is locked.
+/// Insert a semaphore with more permits beforehand to let several tasks hold the lock at once.
+#[derive(Resource, Default)]
+pub struct AsyncLocks(HashMap<TypeId, AsyncSemaphore>);
+
+
+impl AsyncLocks {
+    /// Returns the semaphore of `R`, creating it with one permit if it does not exist.
+    pub fn semaphore<R: 'static>(&mut self) -> AsyncSemaphore {
+        self
+            .0
+            .entry(TypeId::of::<R>())
+            .or_insert_with(|| AsyncSemaphore::new(1))
+            .clone()
+    }
+
+
+    /// Replaces the semaphore of `R`.
+    ///
+    /// Permits already taken from the previous semaphore are returned to it, not to `semaphore`.
+    pub fn insert<R: 'static>(&mut self, semaphore: AsyncSemaphore) {
+        self.0.insert(TypeId::of::<R>(), semaphore);
+    }
+
+
+    /// Returns true if no permit of `R` is available.
+    pub fn is_locked<R: 'static>(&self) -> bool {
+        self
+            .0
+            .get(&TypeId::of::<R>())
+            .is_some_and(|semaphore| semaphore.available() == 0)
+    }
+}
+
+
+impl AsyncSchedules {
+    /// Waits until this task holds the lock of `R`.
+    ///
+    /// `R` is only a key; it is typically the resource or component the tasks take turns to drive,
+    /// and the lock does not prevent systems from accessing it.
+    /// The lock is released when the returned permit is dropped or the task is cancelled.
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_async_system::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct DialogueBox;
+    ///
+    /// fn talk(mut commands: Commands) {
+    ///     commands.spawn_async(|schedules| async move {
+    ///         let _dialogue_box = schedules.lock::<DialogueBox>().await;
+    ///         schedules.add_system(Update, once::run(|| println!("Hello"))).await;
+    ///         schedules.add_system(Update, delay::frames(60)).await;
+    ///     });
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn lock<R: 'static>(&self) -> impl Future<Output=AsyncSemaphorePermit> {
+        let (tx, rx) = futures::channel::mpsc::channel(1);
+        let semaphore = self.push(AsyncScheduleCommand::new(FetchSemaphore::<R>(TaskSender(tx), PhantomData)), rx);
+        async move {
+            semaphore.await.acquire().await
+        }
+    }
+}
+
+
+struct FetchSemaphore<R>(TaskSender<AsyncSemaphore>, PhantomData<fn() -> R>);
+
+
+impl<R: 'static> AsyncSchedule for FetchSemaphore<R> {
+    fn initialize(self: Box<Self>, entity_commands: &mut EntityCommands, _: &mut Schedules) {
+        let mut sender = self.0;
+        entity_commands.add(move |_: Entity, world: &mut World| {
+            let semaphore = world.resource_mut::<AsyncLocks>().semaphore::<R>();
+            let _ = sender.try_send(semaphore);
+        });
+    }
+
+
+    fn kind(&self) -> &'static str {
+        "lock"
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::prelude::{ResMut, Resource};
+
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::{delay, once, wait};
+    use crate::sync::{AsyncLocks, AsyncSemaphore};
+    use crate::test_util::new_deterministic_app;
+
+    struct DialogueBox;
+
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(&'static str, u32)>);
+
+
+    fn log(label: &'static str, id: u32) -> impl Fn(ResMut<Log>) {
+        move |mut log: ResMut<Log>| log.0.push((label, id))
+    }
+
+
+    #[test]
+    fn serialize_by_lock() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Log>();
+        for id in 0..2 {
+            app.spawn_async(move |schedules| async move {
+                let _permit = schedules.lock::<DialogueBox>().await;
+                schedules.add_system(Update, once::run(log("start", id))).await;
+                schedules.add_system(Update, delay::frames(2)).await;
+                schedules.add_system(Update, once::run(log("end", id))).await;
+            });
+        }
+
+        for _ in 0..20 {
+            app.update();
+        }
+        let log = &app.world.resource::<Log>().0;
+        assert_eq!(log.len(), 4);
+        for pair in log.chunks(2) {
+            assert_eq!(pair[0].0, "start");
+            assert_eq!(pair[1], ("end", pair[0].1));
+        }
+    }
+
+
+    #[test]
+    fn release_lock_when_task_cancelled() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Log>();
+        let holder = app.spawn_async(|schedules| async move {
+            let _permit = schedules.lock::<DialogueBox>().await;
+            schedules.add_system(Update, wait::until(|| false)).await;
+        }).id();
+        app.update();
+        app.update();
+        assert!(app.world.resource::<AsyncLocks>().is_locked::<DialogueBox>());
+
+        app.spawn_async(|schedules| async move {
+            let _permit = schedules.lock::<DialogueBox>().await;
+            schedules.add_system(Update, once::run(log("start", 1))).await;
+        });
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app.world.resource::<Log>().0.is_empty());
+
+        app.world.despawn(holder);
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Log>().0, vec![("start", 1)]);
+        assert!(!app.world.resource::<AsyncLocks>().is_locked::<DialogueBox>());
+    }
+
+
+    #[test]
+    fn count_permits() {
+        let semaphore = AsyncSemaphore::new(2);
+        let first = semaphore.try_acquire().unwrap();
+        let _second = semaphore.try_acquire().unwrap();
+        assert!(semaphore.try_acquire().is_none());
+        assert_eq!(semaphore.available(), 0);
+
+        drop(first);
+        assert_eq!(semaphore.available(), 1);
+        assert!(semaphore.try_acquire().is_some());
+    }
+}
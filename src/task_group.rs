@@ -0,0 +1,445 @@
+use std::panic::Location;
+use std::sync::Arc;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::log::error;
+use bevy::prelude::{Children, Component, Entity, Query, Schedules, With};
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures::StreamExt;
+
+use crate::async_schedules::{AsyncSchedules, child_task_command, TaskHandle};
+use crate::runner::{AsyncSchedule, AsyncScheduleCommand, AsyncScheduleCommands, AwaitingOutput};
+
+/// Lists the member tasks of a group created by [`AsyncSchedules::task_group`].
+///
+/// The component is attached to the group entity, which is a child of the task that created the group,
+/// and the member task entities are its children.
+/// Members are added when they start, and move to [`finished`](TaskGroupMembers::finished) once they are despawned.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn show_progress(groups: Query<&TaskGroupMembers>) {
+///     for group in groups.iter() {
+///         println!("{} of {} objectives complete", group.finished().len(), group.len());
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, Eq, PartialEq)]
+pub struct TaskGroupMembers {
+    running: Vec<Entity>,
+    finished: Vec<Entity>,
+}
+
+
+impl TaskGroupMembers {
+    /// Returns the members that are still running.
+    #[inline]
+    pub fn running(&self) -> &[Entity] {
+        &self.running
+    }
+
+
+    /// Returns the members that have finished, in the order they finished.
+    #[inline]
+    pub fn finished(&self) -> &[Entity] {
+        &self.finished
+    }
+
+
+    /// Returns the number of members that have started.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.running.len() + self.finished.len()
+    }
+
+
+    /// Returns true if no member has started.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+
+/// Spawns tasks as a group and waits for some or all of them to finish.
+///
+/// Created by [`AsyncSchedules::task_group`].
+/// A member finishes when its entity is despawned, so a member that is cancelled or despawned counts as finished.
+/// A member that panics also counts as finished; the panic is logged but not propagated.
+/// Members still running when the task that created the group finishes are cancelled.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_async_system::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn_async(|schedules| async move {
+///         let mut objectives = schedules.task_group();
+///         for frames in [60, 120, 180] {
+///             objectives.spawn(move |schedules| async move {
+///                 schedules.add_system(Update, delay::frames(frames)).await;
+///             });
+///         }
+///
+///         objectives.join_n(2).await;
+///         println!("2 of 3 objectives complete");
+///     });
+/// }
+/// ```
+pub struct AsyncTaskGroup {
+    schedules: AsyncSchedules,
+    tx: UnboundedSender<Entity>,
+    rx: UnboundedReceiver<Entity>,
+    spawned: usize,
+    finished: Vec<Entity>,
+    awaiting: Arc<AwaitingOutput>,
+}
+
+
+impl AsyncTaskGroup {
+    /// Spawns a member task.
+    ///
+    /// Like [`AsyncSchedules::spawn_child`], the member starts in the next setup schedule.
+    #[track_caller]
+    pub fn spawn<F>(&mut self, f: impl FnOnce(AsyncSchedules) -> F)
+        where F: std::future::Future<Output=()> + Send + 'static
+    {
+        let command = child_task_command(f, |output| {
+            if let Err(message) = output {
+                error!("A member of the async task group panicked: {message}");
+            }
+        });
+        self.schedules.schedulers.push(AsyncScheduleCommand::new(SpawnGroupMember {
+            command,
+            tx: self.tx.clone(),
+        }).with_caller(Location::caller(), Arc::downgrade(&self.awaiting)));
+        self.spawned += 1;
+    }
+
+
+    /// Returns the number of members spawned.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.spawned
+    }
+
+
+    /// Returns true if no member has been spawned.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.spawned == 0
+    }
+
+
+    /// Returns the number of members known to have finished by the last join.
+    #[inline]
+    pub fn finished(&self) -> usize {
+        self.finished.len()
+    }
+
+
+    /// Waits until all members have finished.
+    pub async fn join_all(&mut self) {
+        self.join_n(self.spawned).await;
+    }
+
+
+    /// Waits until at least one member has finished and returns the entity of the first member that finished.
+    ///
+    /// Returns `None` immediately if the group is empty.
+    /// The entity has been despawned by the time it is returned.
+    pub async fn join_any(&mut self) -> Option<Entity> {
+        self.join_n(1).await;
+        self.finished.first().copied()
+    }
+
+
+    /// Waits until at least `n` members have finished in total.
+    ///
+    /// `n` is clamped to the number of members spawned.
+    pub async fn join_n(&mut self, n: usize) {
+        let n = n.min(self.spawned);
+        while self.finished.len() < n {
+            // `tx` is held by the group, so the stream never ends.
+            if let Some(member) = self.rx.next().await {
+                self.finished.push(member);
+            }
+        }
+    }
+}
+
+
+impl AsyncSchedules {
+    /// Creates an empty [`AsyncTaskGroup`].
+    ///
+    /// The group entity with [`TaskGroupMembers`] is spawned as a child of this task entity in the next setup schedule.
+    #[track_caller]
+    pub fn task_group(&self) -> AsyncTaskGroup {
+        let group = AsyncSchedules::default();
+        let awaiting = Arc::new(AwaitingOutput::default());
+        self.schedulers.push(AsyncScheduleCommand::new(SpawnTaskGroup(group.schedulers.clone()))
+            .with_caller(Location::caller(), Arc::downgrade(&awaiting)));
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        AsyncTaskGroup {
+            schedules: group,
+            tx,
+            rx,
+            spawned: 0,
+            finished: Vec::new(),
+            awaiting,
+        }
+    }
+}
+
+
+/// Turns the runner entity into the group entity.
+///
+/// The commands pushed by [`AsyncTaskGroup::spawn`] are initialized as its children.
+struct SpawnTaskGroup(AsyncScheduleCommands);
+
+
+impl AsyncSchedule for SpawnTaskGroup {
+    fn initialize(self: Box<Self>, entity_commands: &mut EntityCommands, _: &mut Schedules) {
+        entity_commands.insert((self.0, TaskGroupMembers::default()));
+    }
+
+
+    fn kind(&self) -> &'static str {
+        "task_group"
+    }
+}
+
+
+/// Spawns a member task and attaches [`GroupMember`] to it.
+struct SpawnGroupMember {
+    command: AsyncScheduleCommand,
+    tx: UnboundedSender<Entity>,
+}
+
+
+impl AsyncSchedule for SpawnGroupMember {
+    fn initialize(self: Box<Self>, entity_commands: &mut EntityCommands, schedules: &mut Schedules) {
+        entity_commands.insert(GroupMember {
+            entity: entity_commands.id(),
+            tx: self.tx,
+        });
+        self.command.initialize(entity_commands, schedules);
+    }
+
+
+    fn kind(&self) -> &'static str {
+        "task_group::spawn"
+    }
+}
+
+
+/// Notifies the group when the member entity is despawned, however the member ended.
+#[derive(Component)]
+struct GroupMember {
+    entity: Entity,
+    tx: UnboundedSender<Entity>,
+}
+
+
+impl Drop for GroupMember {
+    fn drop(&mut self) {
+        let _ = self.tx.unbounded_send(self.entity);
+    }
+}
+
+
+pub(crate) fn update_task_groups(
+    mut groups: Query<(&mut TaskGroupMembers, Option<&Children>)>,
+    tasks: Query<(), With<TaskHandle>>,
+) {
+    for (mut members, children) in groups.iter_mut() {
+        let running = children
+            .into_iter()
+            .flat_map(|children| children.iter().copied())
+            .filter(|child| tasks.contains(*child))
+            .collect::<Vec<_>>();
+        if running == members.running {
+            continue;
+        }
+
+        let finished = members
+            .running
+            .iter()
+            .copied()
+            .filter(|member| !running.contains(member))
+            .collect::<Vec<_>>();
+        members.finished.extend(finished);
+        members.running = running;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::hierarchy::DespawnRecursiveExt;
+    use bevy::prelude::{Entity, ResMut, Resource};
+
+    use crate::diagnostics::{AsyncRunnerInfo, AsyncTaskDiagnostics};
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::{delay, once, wait};
+    use crate::task_group::TaskGroupMembers;
+    use crate::test_util::new_deterministic_app;
+
+    #[derive(Resource, Default)]
+    struct Joined(Vec<&'static str>);
+
+
+    #[derive(Resource, Default)]
+    struct FirstFinished(Option<Entity>);
+
+
+    fn push(label: &'static str) -> impl Fn(ResMut<Joined>) {
+        move |mut joined: ResMut<Joined>| joined.0.push(label)
+    }
+
+
+    #[test]
+    fn join_any_n_and_all() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Joined>();
+        app.spawn_async(|schedules| async move {
+            let mut group = schedules.task_group();
+            for frames in [1, 3, 5] {
+                group.spawn(move |schedules| async move {
+                    schedules.add_system(Update, delay::frames(frames)).await;
+                });
+            }
+
+            group.join_any().await;
+            schedules.add_system(Update, once::run(push("any"))).await;
+            group.join_n(2).await;
+            schedules.add_system(Update, once::run(push("two"))).await;
+            group.join_all().await;
+            schedules.add_system(Update, once::run(push("all"))).await;
+        });
+
+        for _ in 0..20 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Joined>().0, vec!["any", "two", "all"]);
+    }
+
+
+    #[test]
+    fn join_any_returns_first_finished_member() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<FirstFinished>();
+        app.spawn_async(|schedules| async move {
+            let mut group = schedules.task_group();
+            for frames in [5, 1] {
+                group.spawn(move |schedules| async move {
+                    schedules.add_system(Update, delay::frames(frames)).await;
+                });
+            }
+
+            let member = group.join_any().await;
+            schedules.add_system(Update, once::run(move |mut first: ResMut<FirstFinished>| first.0 = member)).await;
+            group.join_all().await;
+        });
+
+        for _ in 0..6 {
+            app.update();
+        }
+        let members = app.world.query::<&TaskGroupMembers>().single(&app.world).clone();
+        assert_eq!(app.world.resource::<FirstFinished>().0, Some(members.finished()[0]));
+    }
+
+
+    #[test]
+    fn count_despawned_members_as_finished() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<Joined>();
+        app.spawn_async(|schedules| async move {
+            let mut group = schedules.task_group();
+            for _ in 0..2 {
+                group.spawn(|schedules| async move {
+                    schedules.add_system(Update, wait::until(|| false)).await;
+                });
+            }
+            group.join_all().await;
+            schedules.add_system(Update, once::run(push("all"))).await;
+        });
+
+        for _ in 0..3 {
+            app.update();
+        }
+        let members = app.world.query::<&TaskGroupMembers>().single(&app.world).clone();
+        assert_eq!(members.running().len(), 2);
+        for member in members.running() {
+            app.world.entity_mut(*member).despawn_recursive();
+        }
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Joined>().0, vec!["all"]);
+    }
+
+
+    #[test]
+    fn list_group_in_diagnostics() {
+        let mut app = new_deterministic_app();
+        app.init_resource::<AsyncTaskDiagnostics>();
+        let task = app.spawn_async(|schedules| async move {
+            let mut group = schedules.task_group();
+            group.spawn(|schedules| async move {
+                schedules.add_system(Update, wait::until(|| false)).await;
+            });
+            group.join_all().await;
+        }).id();
+
+        for _ in 0..3 {
+            app.update();
+        }
+        let members = app.world.query::<&TaskGroupMembers>().single(&app.world).clone();
+        let diagnostics = app.world.resource::<AsyncTaskDiagnostics>();
+        let awaiting = &diagnostics.get(task).unwrap().awaiting;
+        assert_eq!(awaiting.len(), 1);
+        assert_eq!(awaiting[0].kind, "task_group");
+        assert_eq!(awaiting[0].location.unwrap().file(), file!());
+
+        let member = app.world.get::<AsyncRunnerInfo>(members.running()[0]).unwrap();
+        assert_eq!(member.kind, "task_group::spawn");
+        assert!(member.is_awaited());
+        assert_eq!(member.location.unwrap().file(), file!());
+        assert_eq!(diagnostics.get(members.running()[0]).unwrap().awaiting[0].kind, "wait::until");
+    }
+
+
+    #[test]
+    fn list_members() {
+        let mut app = new_deterministic_app();
+        let task = app.spawn_async(|schedules| async move {
+            let mut group = schedules.task_group();
+            for frames in [0, 10] {
+                group.spawn(move |schedules| async move {
+                    schedules.add_system(Update, delay::frames(frames)).await;
+                });
+            }
+            group.join_all().await;
+        }).id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+        let members = app.world.query::<&TaskGroupMembers>().single(&app.world).clone();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members.finished().len(), 1);
+        assert_eq!(members.running().len(), 1);
+
+        for _ in 0..15 {
+            app.update();
+        }
+        assert!(app.world.get_entity(task).is_none());
+        assert!(app.world.get_entity(members.running()[0]).is_none());
+    }
+}
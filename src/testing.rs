@@ -0,0 +1,229 @@
+//! Helpers for stepping async tasks frame by frame in tests.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use bevy::prelude::*;
+//! use bevy_async_system::prelude::*;
+//! use bevy_async_system::testing::{AsyncTestApp, EventProbe, test_app};
+//!
+//! #[derive(Event, Clone)]
+//! struct Finished;
+//!
+//! let mut app = test_app();
+//! app.add_event::<Finished>();
+//! let task = app.spawn_async(|schedules| async move {
+//!     schedules.add_system(Update, delay::timer(Duration::from_secs(30))).await;
+//!     schedules.add_system(Update, once::send(Finished)).await;
+//! }).id();
+//!
+//! let mut finished = EventProbe::<Finished>::default();
+//! app.update();
+//! app.advance_time(Duration::from_secs(30));
+//! finished.assert_not_sent(&app);
+//! app.update();
+//! finished.assert_sent(&app);
+//! app.run_until_task_finished(task, 1);
+//! ```
+
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::core::{FrameCountPlugin, TaskPoolPlugin};
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::{Entity, Event, Events};
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
+use bevy::utils::default;
+
+use crate::{AsyncSystemPlugin, TaskExecution};
+use crate::async_schedules::TaskHandle;
+use crate::supervisor::PendingRestart;
+
+/// Creates a headless [`App`] for testing async tasks.
+///
+/// It contains only [`TaskPoolPlugin`], [`FrameCountPlugin`], [`TimePlugin`] and [`AsyncSystemPlugin`]
+/// with [`TaskExecution::Deterministic`], so the number of frames each `await` takes is reproducible.
+///
+/// Time does not advance on its own: [`Time::delta`](bevy::time::Time::delta) is zero
+/// unless the frame is run by [`AsyncTestApp::advance_time`].
+pub fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        FrameCountPlugin,
+        TimePlugin,
+        AsyncSystemPlugin {
+            execution: TaskExecution::Deterministic,
+            ..default()
+        }
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    app
+}
+
+
+pub trait AsyncTestApp {
+    /// Runs frames until the task entity is despawned and returns the number of frames run.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the task is still running after `max_frames` frames.
+    fn run_until_task_finished(&mut self, task: Entity, max_frames: u32) -> u32;
+
+
+    /// Runs a single frame in which `delta` elapses.
+    ///
+    /// Requires the time to be driven by [`TimeUpdateStrategy::ManualDuration`], as in [`test_app`].
+    /// If [`AsyncClock::manual`](crate::clock::AsyncClock::manual) is used instead,
+    /// advance the [`ManualAsyncClock`](crate::clock::ManualAsyncClock) directly.
+    fn advance_time(&mut self, delta: Duration);
+}
+
+
+impl AsyncTestApp for App {
+    #[track_caller]
+    fn run_until_task_finished(&mut self, task: Entity, max_frames: u32) -> u32 {
+        for frame in 0..=max_frames {
+            if !is_task_running(self, task) {
+                return frame;
+            }
+            if frame < max_frames {
+                self.update();
+            }
+        }
+        panic!("the task {task:?} did not finish within {max_frames} frames");
+    }
+
+
+    fn advance_time(&mut self, delta: Duration) {
+        let previous = self.world.remove_resource::<TimeUpdateStrategy>().unwrap_or_default();
+        self.world.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+        self.update();
+        self.world.insert_resource(previous);
+    }
+}
+
+
+/// A supervised task waiting out its backoff has no [`TaskHandle`], but it is still running.
+fn is_task_running(app: &App, task: Entity) -> bool {
+    app.world.get::<TaskHandle>(task).is_some() || app.world.get::<PendingRestart>(task).is_some()
+}
+
+
+/// Reads the events of type `E` sent since the last check.
+///
+/// Events are kept for two frames, so check every frame in which an event may be sent.
+pub struct EventProbe<E: Event> {
+    reader: ManualEventReader<E>,
+}
+
+
+impl<E: Event> Default for EventProbe<E> {
+    fn default() -> Self {
+        Self {
+            reader: ManualEventReader::default(),
+        }
+    }
+}
+
+
+impl<E: Event + Clone> EventProbe<E> {
+    /// Returns the events sent since the last check.
+    pub fn read(&mut self, app: &App) -> Vec<E> {
+        self.reader
+            .iter(app.world.resource::<Events<E>>())
+            .cloned()
+            .collect()
+    }
+
+
+    /// Asserts that at least one event has been sent since the last check.
+    #[track_caller]
+    pub fn assert_sent(&mut self, app: &App) -> Vec<E> {
+        let events = self.read(app);
+        assert!(!events.is_empty(), "expected {} to be sent", std::any::type_name::<E>());
+        events
+    }
+
+
+    /// Asserts that no event has been sent since the last check.
+    #[track_caller]
+    pub fn assert_not_sent(&mut self, app: &App) {
+        let count = self.read(app).len();
+        assert_eq!(count, 0, "expected no {} to be sent, but {count} were sent", std::any::type_name::<E>());
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use bevy::app::Update;
+    use bevy::prelude::Event;
+
+    use crate::prelude::*;
+    use crate::testing::{AsyncTestApp, EventProbe, test_app};
+
+    #[derive(Event, Clone, Debug, Eq, PartialEq)]
+    struct Sent(u32);
+
+
+    #[test]
+    fn time_does_not_advance_by_itself() {
+        let mut app = test_app();
+        app.add_event::<Sent>();
+        let task = app.spawn_async(|schedules| async move {
+            schedules.add_system(Update, delay::timer(Duration::from_secs(30))).await;
+            schedules.add_system(Update, once::send(Sent(1))).await;
+        }).id();
+
+        let mut probe = EventProbe::<Sent>::default();
+        for _ in 0..10 {
+            app.update();
+        }
+        probe.assert_not_sent(&app);
+
+        app.advance_time(Duration::from_secs(29));
+        app.update();
+        probe.assert_not_sent(&app);
+        app.advance_time(Duration::from_secs(1));
+        app.update();
+        assert_eq!(probe.assert_sent(&app), vec![Sent(1)]);
+        assert_eq!(app.run_until_task_finished(task, 1), 1);
+    }
+
+
+    #[test]
+    fn wait_for_supervised_task_during_backoff() {
+        let mut app = test_app();
+        let panicked = Arc::new(AtomicBool::new(false));
+        let task = app
+            .spawn_async_restartable(move |schedules| {
+                let panicked = panicked.clone();
+                async move {
+                    schedules.add_system(Update, once::run(|| {})).await;
+                    if !panicked.swap(true, Ordering::Relaxed) {
+                        panic!("broken");
+                    }
+                }
+            })
+            .insert(Supervisor::new(RestartPolicy::OnPanic).with_backoff(Backoff::Frames(5)))
+            .id();
+
+        assert!(5 < app.run_until_task_finished(task, 20));
+    }
+
+
+    #[test]
+    #[should_panic(expected = "did not finish within 10 frames")]
+    fn panic_if_task_does_not_finish() {
+        let mut app = test_app();
+        let task = app.spawn_async(|schedules| async move {
+            schedules.add_system(Update, wait::until(|| false)).await;
+        }).id();
+        app.run_until_task_finished(task, 10);
+    }
+}
@@ -0,0 +1,367 @@
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+use bevy::core::{FrameCount, Name};
+use bevy::ecs::archetype::ArchetypeComponentId;
+use bevy::ecs::component::{ComponentId, Tick};
+use bevy::ecs::query::Access;
+use bevy::ecs::schedule::SystemSet;
+use bevy::ecs::world::unsafe_world_cell::UnsafeWorldCell;
+use bevy::prelude::{Changed, Commands, Component, Deref, Entity, Query, Res, System, World};
+use bevy::utils::tracing::{field, info_span, Span};
+
+use crate::diagnostics::AsyncRunnerInfo;
+
+/// The span of an async task.
+///
+/// The future of the task is instrumented with this span.
+/// The name given to [`spawn_async_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_named)
+/// or [`spawn_async_restartable_named`](crate::ext::spawn_async_system::SpawnAsyncSystem::spawn_async_restartable_named)
+/// is recorded in the `name` field when the task is spawned,
+/// and the [`Name`] of the task is recorded again each time the task is started or restarted with a new span,
+/// so naming the task makes it easy to find in tracy or chrome-trace output.
+#[derive(Component, Deref, Debug, Clone)]
+pub struct TaskSpan(pub(crate) Span);
+
+
+impl TaskSpan {
+    #[inline]
+    pub(crate) fn new(name: Option<&str>) -> Self {
+        Self(info_span!("async_task", name, task = field::Empty))
+    }
+}
+
+
+/// The span of a system awaited by a task, a child of [`TaskSpan`].
+///
+/// The system enters it each time it runs.
+/// It is closed when the system's output is received, recording the number of frames elapsed.
+#[derive(Component, Debug, Clone)]
+pub struct RunnerSpan(Arc<Mutex<Span>>);
+
+
+impl RunnerSpan {
+    pub(crate) fn new(info: &AsyncRunnerInfo, task: Option<&TaskSpan>) -> Self {
+        let span = info_span!(
+            parent: task.and_then(|span| span.id()),
+            "async_system",
+            kind = info.kind,
+            system = info.system,
+            schedule = info.schedule.as_ref().map(field::debug),
+            frames = field::Empty,
+        );
+        Self(Arc::new(Mutex::new(span)))
+    }
+
+
+    /// Returns the span, which is disabled once it has been closed.
+    #[inline]
+    pub fn span(&self) -> Span {
+        self.0.lock().unwrap().clone()
+    }
+
+
+    /// Takes the span out, so that it closes when the returned handle is dropped.
+    #[inline]
+    fn close(&self) -> Span {
+        std::mem::replace(&mut *self.0.lock().unwrap(), Span::none())
+    }
+}
+
+
+/// Runs `system` inside the [`RunnerSpan`] of the runner `entity`.
+///
+/// The span is looked up when the system is initialized, which happens after the runner entity has been spawned.
+pub(crate) struct Traced<S> {
+    system: S,
+    entity: Entity,
+    span: Option<RunnerSpan>,
+}
+
+
+impl<S> Traced<S> {
+    #[inline]
+    pub(crate) const fn new(entity: Entity, system: S) -> Self {
+        Self {
+            system,
+            entity,
+            span: None,
+        }
+    }
+
+
+    #[inline]
+    fn span(&self) -> Span {
+        self.span.as_ref().map(RunnerSpan::span).unwrap_or_else(Span::none)
+    }
+}
+
+
+impl<S: System> System for Traced<S> {
+    type In = S::In;
+    type Out = S::Out;
+
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    #[inline]
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system.component_access()
+    }
+
+    #[inline]
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    #[inline]
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    #[inline]
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: Self::In, world: UnsafeWorldCell) -> Self::Out {
+        let span = self.span();
+        let _entered = span.enter();
+        // SAFETY: the caller upholds the contract for the wrapped system, whose access this system reports.
+        self.system.run_unsafe(input, world)
+    }
+
+    fn run(&mut self, input: Self::In, world: &mut World) -> Self::Out {
+        let span = self.span();
+        let _entered = span.enter();
+        self.system.run(input, world)
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+        self.span = world.get::<RunnerSpan>(self.entity).cloned();
+    }
+
+    #[inline]
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+    }
+
+    #[inline]
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    #[inline]
+    fn default_system_sets(&self) -> Vec<Box<dyn SystemSet>> {
+        self.system.default_system_sets()
+    }
+
+    #[inline]
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    #[inline]
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+}
+
+
+pub(crate) fn update_spans(
+    mut commands: Commands,
+    frame_count: Option<Res<FrameCount>>,
+    new_tasks: Query<(Entity, &TaskSpan, Option<&Name>), Changed<TaskSpan>>,
+    runners: Query<(Entity, &AsyncRunnerInfo, &RunnerSpan)>,
+) {
+    for (task, span, name) in new_tasks.iter() {
+        span.record("task", field::debug(task));
+        if let Some(name) = name {
+            span.record("name", name.as_str());
+        }
+    }
+
+    for (entity, info, span) in runners.iter() {
+        if info.is_awaited() {
+            continue;
+        }
+        let span = span.close();
+        if let (Some(started), Some(frame_count)) = (info.started_frame, frame_count.as_deref()) {
+            span.record("frames", frame_count.0.wrapping_sub(started));
+        }
+        commands.entity(entity).remove::<RunnerSpan>();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::sync::{Mutex, OnceLock};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use bevy::app::Update;
+    use bevy::prelude::With;
+    use bevy::utils::HashMap;
+    use bevy::utils::tracing::{Event, Id, Metadata, Subscriber};
+    use bevy::utils::tracing::dispatcher::{Dispatch, set_global_default};
+    use bevy::utils::tracing::field::{Field, Visit};
+    use bevy::utils::tracing::span::{Attributes, Record};
+
+    use crate::diagnostics::AsyncRunnerInfo;
+    use crate::ext::spawn_async_system::WorldSpawnAsyncSystem;
+    use crate::runner::{once, wait};
+    use crate::test_util::new_deterministic_app;
+    use crate::trace::{RunnerSpan, TaskSpan};
+
+    #[derive(Debug, Clone, Default)]
+    struct RecordedSpan {
+        name: &'static str,
+        parent: Option<u64>,
+        fields: HashMap<&'static str, String>,
+        entered: usize,
+    }
+
+
+    struct FieldRecorder<'a>(&'a mut HashMap<&'static str, String>);
+
+
+    impl Visit for FieldRecorder<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.insert(field.name(), format!("{value:?}"));
+        }
+    }
+
+
+    /// Records every span in the process, since the systems run on the threads of the task pools.
+    #[derive(Default)]
+    struct SpanRecorder {
+        next_id: AtomicU64,
+        spans: Mutex<HashMap<u64, RecordedSpan>>,
+    }
+
+
+    impl SpanRecorder {
+        fn global() -> &'static Self {
+            static RECORDER: OnceLock<&'static SpanRecorder> = OnceLock::new();
+            RECORDER.get_or_init(|| {
+                let recorder: &'static SpanRecorder = Box::leak(Box::default());
+                set_global_default(Dispatch::new(GlobalRecorder(recorder))).unwrap();
+                recorder
+            })
+        }
+
+
+        fn spans(&self) -> Vec<(u64, RecordedSpan)> {
+            self.spans.lock().unwrap().iter().map(|(id, span)| (*id, span.clone())).collect()
+        }
+    }
+
+
+    struct GlobalRecorder(&'static SpanRecorder);
+
+
+    impl Subscriber for GlobalRecorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let id = self.0.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+            let mut recorded = RecordedSpan {
+                name: span.metadata().name(),
+                parent: span.parent().map(Id::into_u64),
+                ..Default::default()
+            };
+            span.record(&mut FieldRecorder(&mut recorded.fields));
+            self.0.spans.lock().unwrap().insert(id, recorded);
+            Id::from_u64(id)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            if let Some(recorded) = self.0.spans.lock().unwrap().get_mut(&span.into_u64()) {
+                values.record(&mut FieldRecorder(&mut recorded.fields));
+            }
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            if let Some(recorded) = self.0.spans.lock().unwrap().get_mut(&span.into_u64()) {
+                recorded.entered += 1;
+            }
+        }
+
+        fn exit(&self, _: &Id) {}
+    }
+
+
+    #[test]
+    fn open_and_close_runner_spans() {
+        let mut app = new_deterministic_app();
+        app.spawn_async(|schedules| async move {
+            schedules.add_system(Update, once::run(|| {})).await;
+            schedules.add_system(Update, wait::until(|| false)).await;
+        });
+
+        app.update();
+        assert_eq!(app.world.query::<&TaskSpan>().iter(&app.world).count(), 1);
+        assert_eq!(app.world.query::<&RunnerSpan>().iter(&app.world).count(), 1);
+
+        app.update();
+        assert_eq!(app.world.query::<&RunnerSpan>().iter(&app.world).count(), 1);
+        assert_eq!(app.world.query_filtered::<(), With<AsyncRunnerInfo>>().iter(&app.world).count(), 2);
+    }
+
+
+    #[test]
+    fn record_span_fields() {
+        let recorder = SpanRecorder::global();
+        let mut app = new_deterministic_app();
+        let task = app.spawn_async_named("record_span_fields", |schedules| async move {
+            schedules.add_system(Update, once::run(|| {})).await;
+            schedules.add_system(Update, wait::until(|| false)).await;
+        }).id();
+
+        app.update();
+        app.update();
+
+        let spans = recorder.spans();
+        let (task_id, task_span) = spans
+            .iter()
+            .find(|(_, span)| span.fields.get("name").is_some_and(|name| name == "record_span_fields"))
+            .unwrap();
+        assert_eq!(task_span.name, "async_task");
+        assert_eq!(task_span.fields["task"], format!("{task:?}"));
+
+        let (_, runner_span) = spans
+            .iter()
+            .find(|(_, span)| span.parent == Some(*task_id) && span.fields["kind"] == "once::run")
+            .unwrap();
+        assert_eq!(runner_span.name, "async_system");
+        assert!(runner_span.fields["system"].contains("record_span_fields"));
+        assert_eq!(runner_span.fields["schedule"], "Update");
+        assert!(runner_span.fields.contains_key("frames"));
+        assert_eq!(runner_span.entered, 1);
+    }
+}
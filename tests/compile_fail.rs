@@ -0,0 +1,20 @@
+#[test]
+fn compile_fail() {
+    let t = trybuild2::TestCases::new();
+    t.compile_fail_check_sub(
+        "tests/compile_fail/await_forever_output.rs",
+        "cannot be awaited for an output of type `u32`",
+    );
+    t.compile_fail_check_sub(
+        "tests/compile_fail/non_send_future.rs",
+        "cannot be sent between threads safely",
+    );
+    t.compile_fail_check_sub(
+        "tests/compile_fail/non_send_system.rs",
+        "cannot be sent between threads safely",
+    );
+    t.compile_fail_check_sub(
+        "tests/compile_fail/non_static_capture.rs",
+        "does not live long enough",
+    );
+}
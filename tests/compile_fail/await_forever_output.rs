@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use bevy_async_system::prelude::*;
+
+fn count_up() {}
+
+fn setup(mut commands: Commands) {
+    commands.spawn_async(|schedules| async move {
+        let _count = schedules.add_system::<u32>(Update, repeat::forever(count_up)).await;
+    });
+}
+
+fn main() {
+    App::new().add_systems(Startup, setup);
+}
@@ -0,0 +1,16 @@
+use std::rc::Rc;
+
+use bevy::prelude::*;
+use bevy_async_system::prelude::*;
+
+fn setup(mut commands: Commands) {
+    commands.spawn_async(|schedules| async move {
+        let shared = Rc::new(0);
+        schedules.add_system(Update, delay::frames(1)).await;
+        println!("{shared}");
+    });
+}
+
+fn main() {
+    App::new().add_systems(Startup, setup);
+}
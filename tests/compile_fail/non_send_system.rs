@@ -0,0 +1,17 @@
+use std::rc::Rc;
+
+use bevy::prelude::*;
+use bevy_async_system::prelude::*;
+
+fn setup(mut commands: Commands) {
+    commands.spawn_async(|schedules| async move {
+        let shared = Rc::new(0);
+        schedules.add_system(Update, once::run(move || {
+            println!("{shared}");
+        })).await;
+    });
+}
+
+fn main() {
+    App::new().add_systems(Startup, setup);
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use bevy_async_system::prelude::*;
+
+fn setup(mut commands: Commands) {
+    let message = String::from("Hello");
+    let message = &message;
+    commands.spawn_async(|schedules| async move {
+        schedules.add_system(Update, once::run(|| {})).await;
+        println!("{message}");
+    });
+}
+
+fn main() {
+    App::new().add_systems(Startup, setup);
+}